@@ -1,6 +1,12 @@
 extern crate bindgen;
 
+use bindgen::callbacks::EnumVariantValue;
+
+use std::cell::RefCell;
+use std::collections::BTreeMap;
 use std::env;
+use std::fs;
+use std::rc::Rc;
 
 fn main() {
 	println!("cargo:rerun-if-env-changed=SLANG_DIR");
@@ -36,6 +42,9 @@ fn main() {
 
 	let out_dir = env::var("OUT_DIR").expect("Couldn't determine output directory.");
 
+	let parse_callback = ParseCallback::default();
+	let compiler_option_names = parse_callback.compiler_option_names.clone();
+
 	bindgen::builder()
 		.header(format!("{include_dir}/slang.h").as_str())
 		.clang_arg("-v")
@@ -45,13 +54,19 @@ fn main() {
 		.allowlist_function("spComputeStringHash")
 		.allowlist_function("slang_.*")
 		.allowlist_type("slang.*")
+		.allowlist_type("SlangPathType")
+		.allowlist_type("OSPathKind")
+		.allowlist_type("PathKind")
+		.allowlist_type("SlangDebugInfoFormat")
+		.allowlist_type("SlangEmitSpirvMethod")
+		.allowlist_type("SlangFpDenormalMode")
 		.allowlist_var("SLANG_.*")
 		.with_codegen_config(
 			bindgen::CodegenConfig::FUNCTIONS
 				| bindgen::CodegenConfig::TYPES
 				| bindgen::CodegenConfig::VARS,
 		)
-		.parse_callbacks(Box::new(ParseCallback {}))
+		.parse_callbacks(Box::new(parse_callback))
 		.default_enum_style(bindgen::EnumVariation::Rust {
 			non_exhaustive: false,
 		})
@@ -64,17 +79,36 @@ fn main() {
 		.expect("Couldn't generate bindings.")
 		.write_to_file(format!("{out_dir}/bindings.rs").as_str())
 		.expect("Couldn't write bindings.");
+
+	// List every compiler option of the linked Slang version, so missing wrappers can be detected.
+	let compiler_option_names = compiler_option_names
+		.borrow()
+		.values()
+		.filter(|name| !name.starts_with("CountOf"))
+		.map(|name| format!("\tslang_CompilerOptionName::{name},\n"))
+		.collect::<String>();
+
+	let code = format!(
+		"/// Every `slang::CompilerOptionName` of the linked Slang version, without the `CountOf` markers.\n\
+		 pub const COMPILER_OPTION_NAMES: &[slang_CompilerOptionName] = &[\n{compiler_option_names}];\n"
+	);
+
+	fs::write(format!("{out_dir}/compiler_option_names.rs"), code)
+		.expect("Couldn't write compiler option names.");
 }
 
-#[derive(Debug)]
-struct ParseCallback {}
+#[derive(Debug, Default)]
+struct ParseCallback {
+	/// The variants of `slang::CompilerOptionName` by value, including the `CountOf` markers.
+	compiler_option_names: Rc<RefCell<BTreeMap<i64, String>>>,
+}
 
 impl bindgen::callbacks::ParseCallbacks for ParseCallback {
 	fn enum_variant_name(
 		&self,
 		enum_name: Option<&str>,
 		original_variant_name: &str,
-		_variant_value: bindgen::callbacks::EnumVariantValue,
+		variant_value: EnumVariantValue,
 	) -> Option<String> {
 		let enum_name = enum_name?;
 
@@ -83,10 +117,21 @@ impl bindgen::callbacks::ParseCallbacks for ParseCallback {
 		let mut map = std::collections::HashMap::new();
 		map.insert("SlangMatrixLayoutMode", "SlangMatrixLayout");
 		map.insert("SlangCompileTarget", "Slang");
+		map.insert("SlangEmitSpirvMethod", "SlangEmitSpirv");
+		map.insert("SlangFpDenormalMode", "SlangFpDenormMode");
 
 		let trim = map.get(enum_name).unwrap_or(&enum_name);
 		let new_variant_name = pascal_case_from_snake_case(original_variant_name);
 		let new_variant_name = new_variant_name.trim_start_matches(trim);
+
+		if let (Some("CompilerOptionName"), EnumVariantValue::Signed(value)) =
+			(enum_name.strip_prefix("slang::"), variant_value)
+		{
+			self.compiler_option_names
+				.borrow_mut()
+				.insert(value, new_variant_name.to_string());
+		}
+
 		Some(new_variant_name.to_string())
 	}
 
@@ -0,0 +1,149 @@
+//! Helpers for implementing Slang interfaces with Rust objects.
+
+use std::ffi::c_void;
+use std::ptr::null_mut;
+use std::sync::atomic::{AtomicU32, Ordering, fence};
+
+use crate::{Blob, IUnknown, Interface, UUID, sys};
+
+/// Describes a Rust type that is exposed to Slang as a reference counted object.
+///
+/// # Safety
+///
+/// The object must actually implement every interface listed in `INTERFACES`,
+/// meaning its vtable is compatible with the vtable of each of those interfaces.
+pub(crate) unsafe trait Implements: Sized {
+	const INTERFACES: &'static [UUID];
+}
+
+#[repr(C)]
+pub(crate) struct Object<T> {
+	vtable: *const c_void,
+	ref_count: AtomicU32,
+	value: T,
+}
+
+impl<T: Implements> Object<T> {
+	/// Allocates a new object with a reference count of one.
+	pub(crate) fn allocate<V>(vtable: &'static V, value: T) -> IUnknown {
+		let object = Box::new(Object {
+			vtable: vtable as *const V as *const c_void,
+			ref_count: AtomicU32::new(1),
+			value,
+		});
+
+		IUnknown(std::ptr::NonNull::new(Box::into_raw(object) as *mut c_void).unwrap())
+	}
+
+	/// # Safety
+	///
+	/// `this` must point to an `Object<T>` that stays alive for `'a`.
+	pub(crate) unsafe fn value<'a, P>(this: *mut P) -> &'a T {
+		unsafe { &(*(this as *const Object<T>)).value }
+	}
+}
+
+fn uuid_eq(a: &UUID, b: &UUID) -> bool {
+	a.data1 == b.data1 && a.data2 == b.data2 && a.data3 == b.data3 && a.data4 == b.data4
+}
+
+pub(crate) const fn unknown_vtable<T: Implements>() -> sys::ISlangUnknown__bindgen_vtable {
+	sys::ISlangUnknown__bindgen_vtable {
+		ISlangUnknown_queryInterface: query_interface::<T>,
+		ISlangUnknown_addRef: add_ref::<T>,
+		ISlangUnknown_release: release::<T>,
+	}
+}
+
+pub(crate) const fn castable_vtable<T: Implements>() -> sys::ICastableVtable {
+	sys::ICastableVtable {
+		_base: unknown_vtable::<T>(),
+		castAs: cast_as::<T>,
+	}
+}
+
+unsafe extern "C" fn query_interface<T: Implements>(
+	this: *mut sys::ISlangUnknown,
+	uuid: *const UUID,
+	out_object: *mut *mut c_void,
+) -> sys::SlangResult {
+	let object = unsafe { cast_as::<T>(this as *mut c_void, uuid) };
+
+	if object.is_null() {
+		unsafe { *out_object = null_mut() };
+		crate::E_NO_INTERFACE
+	} else {
+		unsafe { add_ref::<T>(this) };
+		unsafe { *out_object = object };
+		crate::OK
+	}
+}
+
+unsafe extern "C" fn add_ref<T: Implements>(this: *mut sys::ISlangUnknown) -> u32 {
+	let object = unsafe { &*(this as *const Object<T>) };
+	object.ref_count.fetch_add(1, Ordering::Relaxed) + 1
+}
+
+unsafe extern "C" fn release<T: Implements>(this: *mut sys::ISlangUnknown) -> u32 {
+	let object = unsafe { &*(this as *const Object<T>) };
+	let count = object.ref_count.fetch_sub(1, Ordering::Release) - 1;
+
+	if count == 0 {
+		fence(Ordering::Acquire);
+		drop(unsafe { Box::from_raw(this as *mut Object<T>) });
+	}
+
+	count
+}
+
+unsafe extern "C" fn cast_as<T: Implements>(this: *mut c_void, guid: *const UUID) -> *mut c_void {
+	let guid = unsafe { &*guid };
+
+	if T::INTERFACES.iter().any(|iid| uuid_eq(iid, guid)) {
+		this
+	} else {
+		null_mut()
+	}
+}
+
+/// Hands ownership of the reference held by `value` to the caller.
+pub(crate) fn into_raw<I: Interface, T>(value: I) -> *mut T {
+	let ptr = unsafe { value.as_raw() };
+	std::mem::forget(value);
+	ptr
+}
+
+/// Backing storage of a [`Blob`] created from Rust data.
+///
+/// A NUL byte is kept past the end of the data, so blobs holding text can also
+/// be read as C strings, like the blobs Slang creates itself.
+struct BlobData {
+	data: Vec<u8>,
+	size: usize,
+}
+
+unsafe impl Implements for BlobData {
+	const INTERFACES: &'static [UUID] = &[IUnknown::IID, Blob::IID];
+}
+
+static BLOB_VTABLE: sys::IBlobVtable = sys::IBlobVtable {
+	_base: unknown_vtable::<BlobData>(),
+	getBufferPointer: blob_buffer_pointer,
+	getBufferSize: blob_buffer_size,
+};
+
+unsafe extern "C" fn blob_buffer_pointer(this: *mut c_void) -> *const c_void {
+	let blob = unsafe { Object::<BlobData>::value(this) };
+	blob.data.as_ptr() as *const c_void
+}
+
+unsafe extern "C" fn blob_buffer_size(this: *mut c_void) -> usize {
+	let blob = unsafe { Object::<BlobData>::value(this) };
+	blob.size
+}
+
+pub(crate) fn blob_from_vec(mut data: Vec<u8>) -> Blob {
+	let size = data.len();
+	data.push(0);
+	Blob(Object::allocate(&BLOB_VTABLE, BlobData { data, size }))
+}
@@ -0,0 +1,230 @@
+use std::ffi::CString;
+use std::path::Path;
+
+use crate::{
+	CompileTarget, CompilerOptions, Error, ErrorCode, FloatingPointMode, LineDirectiveMode,
+	MatrixLayoutMode, OwnedSessionDesc, PreprocessorMacros, ProfileID, Result, SessionDesc,
+	SessionFlags, TargetDesc, TargetFlags, VirtualFileSystem,
+};
+
+/// Implemented by the session descriptions accepted by [`crate::GlobalSession::create_session`].
+pub trait AsSessionDesc {
+	/// Calls `f` with a [`SessionDesc`] that may borrow from `self`.
+	fn with_session_desc<R>(&self, f: impl FnOnce(&SessionDesc) -> R) -> R;
+}
+
+impl AsSessionDesc for SessionDesc<'_> {
+	fn with_session_desc<R>(&self, f: impl FnOnce(&SessionDesc) -> R) -> R {
+		f(self)
+	}
+}
+
+impl AsSessionDesc for OwnedSessionDesc {
+	fn with_session_desc<R>(&self, f: impl FnOnce(&SessionDesc) -> R) -> R {
+		f(self.desc())
+	}
+}
+
+/// An owned alternative to [`TargetDesc`].
+#[derive(Clone)]
+pub struct TargetConfig {
+	format: CompileTarget,
+	profile: ProfileID,
+	flags: TargetFlags,
+	floating_point_mode: Option<FloatingPointMode>,
+	line_directive_mode: Option<LineDirectiveMode>,
+	force_glsl_scalar_buffer_layout: bool,
+	options: CompilerOptions,
+}
+
+impl Default for TargetConfig {
+	fn default() -> Self {
+		Self {
+			format: CompileTarget::Unknown,
+			profile: ProfileID::UNKNOWN,
+			flags: TargetFlags::empty(),
+			floating_point_mode: None,
+			line_directive_mode: None,
+			force_glsl_scalar_buffer_layout: false,
+			options: CompilerOptions::default(),
+		}
+	}
+}
+
+impl TargetConfig {
+	pub fn format(mut self, format: CompileTarget) -> Self {
+		self.format = format;
+		self
+	}
+
+	pub fn profile(mut self, profile: ProfileID) -> Self {
+		self.profile = profile;
+		self
+	}
+
+	pub fn flags(mut self, flags: TargetFlags) -> Self {
+		self.flags = flags;
+		self
+	}
+
+	pub fn floating_point_mode(mut self, mode: FloatingPointMode) -> Self {
+		self.floating_point_mode = Some(mode);
+		self
+	}
+
+	pub fn line_directive_mode(mut self, mode: LineDirectiveMode) -> Self {
+		self.line_directive_mode = Some(mode);
+		self
+	}
+
+	pub fn force_glsl_scalar_buffer_layout(mut self, enable: bool) -> Self {
+		self.force_glsl_scalar_buffer_layout = enable;
+		self
+	}
+
+	pub fn options(mut self, options: CompilerOptions) -> Self {
+		self.options = options;
+		self
+	}
+
+	fn desc(&self) -> TargetDesc<'_> {
+		let mut desc = TargetDesc::default()
+			.format(self.format)
+			.profile(self.profile)
+			.flags(self.flags)
+			.force_glsl_scalar_buffer_layout(self.force_glsl_scalar_buffer_layout)
+			.options(&self.options);
+
+		if let Some(mode) = self.floating_point_mode {
+			desc = desc.floating_point_mode(mode);
+		}
+
+		if let Some(mode) = self.line_directive_mode {
+			desc = desc.line_directive_mode(mode);
+		}
+
+		desc
+	}
+}
+
+/// An owned alternative to [`SessionDesc`], which can be kept around and reused.
+///
+/// The FFI description is only built while [`crate::GlobalSession::create_session`] runs.
+#[derive(Clone, Default)]
+pub struct SessionConfig {
+	targets: Vec<TargetConfig>,
+	search_paths: Vec<CString>,
+	options: CompilerOptions,
+	preprocessor_macros: PreprocessorMacros,
+	flags: SessionFlags,
+	default_matrix_layout_mode: Option<MatrixLayoutMode>,
+	file_system: Option<VirtualFileSystem>,
+	allow_glsl_syntax: bool,
+	enable_effect_annotations: bool,
+	skip_spirv_validation: bool,
+}
+
+impl SessionConfig {
+	/// Adds a target, in the same order as the target indices used by [`crate::ComponentType`].
+	pub fn target(mut self, target: TargetConfig) -> Self {
+		self.targets.push(target);
+		self
+	}
+
+	/// Fails with [`ErrorCode::InvalidArg`] if the path contains a NUL byte,
+	/// or on Windows, if it isn't valid Unicode.
+	pub fn search_path(mut self, path: impl AsRef<Path>) -> Result<Self> {
+		self.search_paths.push(path_c_string(path.as_ref())?);
+		Ok(self)
+	}
+
+	pub fn options(mut self, options: CompilerOptions) -> Self {
+		self.options = options;
+		self
+	}
+
+	pub fn preprocessor_macros(mut self, macros: PreprocessorMacros) -> Self {
+		self.preprocessor_macros = macros;
+		self
+	}
+
+	pub fn flags(mut self, flags: SessionFlags) -> Self {
+		self.flags = flags;
+		self
+	}
+
+	pub fn default_matrix_layout_mode(mut self, mode: MatrixLayoutMode) -> Self {
+		self.default_matrix_layout_mode = Some(mode);
+		self
+	}
+
+	pub fn file_system(mut self, file_system: VirtualFileSystem) -> Self {
+		self.file_system = Some(file_system);
+		self
+	}
+
+	pub fn allow_glsl_syntax(mut self, enable: bool) -> Self {
+		self.allow_glsl_syntax = enable;
+		self
+	}
+
+	pub fn enable_effect_annotations(mut self, enable: bool) -> Self {
+		self.enable_effect_annotations = enable;
+		self
+	}
+
+	pub fn skip_spirv_validation(mut self, skip: bool) -> Self {
+		self.skip_spirv_validation = skip;
+		self
+	}
+}
+
+impl AsSessionDesc for SessionConfig {
+	fn with_session_desc<R>(&self, f: impl FnOnce(&SessionDesc) -> R) -> R {
+		let targets = self
+			.targets
+			.iter()
+			.map(TargetConfig::desc)
+			.collect::<Vec<_>>();
+
+		let search_path_ptrs = self
+			.search_paths
+			.iter()
+			.map(|path| path.as_ptr().cast())
+			.collect::<Vec<_>>();
+
+		let mut desc = SessionDesc::default()
+			.targets(&targets)
+			.search_paths(&search_path_ptrs)
+			.options(&self.options)
+			.preprocessor_macros(&self.preprocessor_macros)
+			.flags(self.flags)
+			.allow_glsl_syntax(self.allow_glsl_syntax)
+			.enable_effect_annotations(self.enable_effect_annotations)
+			.skip_spirv_validation(self.skip_spirv_validation);
+
+		if let Some(mode) = self.default_matrix_layout_mode {
+			desc = desc.default_matrix_layout_mode(mode);
+		}
+
+		if let Some(file_system) = &self.file_system {
+			desc = desc.file_system(file_system);
+		}
+
+		f(&desc)
+	}
+}
+
+/// Converts a path to the bytes Slang expects, which are UTF-8 on Windows.
+pub(crate) fn path_c_string(path: &Path) -> Result<CString> {
+	#[cfg(unix)]
+	let bytes = std::os::unix::ffi::OsStrExt::as_bytes(path.as_os_str());
+
+	#[cfg(not(unix))]
+	let bytes = path
+		.to_str()
+		.ok_or(Error::Code(ErrorCode::InvalidArg))?
+		.as_bytes();
+
+	CString::new(bytes).map_err(|_| Error::Code(ErrorCode::InvalidArg))
+}
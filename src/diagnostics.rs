@@ -0,0 +1,306 @@
+use std::ffi::c_void;
+use std::mem::ManuallyDrop;
+use std::sync::atomic::{AtomicU64, AtomicUsize, Ordering};
+use std::sync::{Arc, Mutex};
+
+use crate::{Blob, IUnknown, Interface, Session, sys};
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub enum Severity {
+	Note,
+	Warning,
+	Error,
+	Fatal,
+	Internal,
+}
+
+impl Severity {
+	// Ordered so that names which contain another name are matched first.
+	const NAMES: [(&'static str, Severity); 5] = [
+		("internal error", Severity::Internal),
+		("fatal error", Severity::Fatal),
+		("error", Severity::Error),
+		("warning", Severity::Warning),
+		("note", Severity::Note),
+	];
+
+	fn name(self) -> &'static str {
+		Self::NAMES
+			.iter()
+			.find(|(_, severity)| *severity == self)
+			.map(|(name, _)| *name)
+			.unwrap()
+	}
+}
+
+impl std::fmt::Display for Severity {
+	fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+		f.write_str(self.name())
+	}
+}
+
+/// A single message parsed from the textual diagnostics Slang produces.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct Diagnostic {
+	pub file: Option<String>,
+	/// One-based line number.
+	pub line: Option<u32>,
+	/// One-based column number, taken from the caret Slang prints below the source line.
+	pub column: Option<u32>,
+	pub severity: Severity,
+	pub code: Option<u32>,
+	pub message: String,
+}
+
+impl Diagnostic {
+	/// Parses diagnostics in the `file(line): severity code: message` format Slang emits.
+	/// Lines that are not part of a diagnostic are ignored.
+	pub fn parse(text: &str) -> Vec<Diagnostic> {
+		let mut diagnostics = Vec::new();
+		let mut source_line = None;
+
+		for line in text.lines() {
+			if let Some(diagnostic) = Self::parse_header(line) {
+				diagnostics.push(diagnostic);
+				source_line = None;
+				continue;
+			}
+
+			let Some(last) = diagnostics.last_mut() else {
+				continue;
+			};
+
+			let is_caret_line = line.trim_start().starts_with('^')
+				&& line.trim().chars().all(|c| c == '^' || c == '~');
+
+			if source_line.is_some() && is_caret_line && last.column.is_none() {
+				let column = line.chars().take_while(|&c| c != '^').count();
+				last.column = Some(column as u32 + 1);
+			}
+
+			source_line = Some(line);
+		}
+
+		diagnostics
+	}
+
+	pub fn parse_blob(blob: &Blob) -> Vec<Diagnostic> {
+		Self::parse(&String::from_utf8_lossy(blob.as_slice()))
+	}
+
+	fn parse_header(line: &str) -> Option<Diagnostic> {
+		for (start, _) in line.char_indices() {
+			if start != 0 && !line[..start].ends_with(": ") {
+				continue;
+			}
+
+			for (name, severity) in Severity::NAMES {
+				let Some(rest) = line[start..].strip_prefix(name) else {
+					continue;
+				};
+
+				let (code, rest) = match rest.strip_prefix(' ') {
+					Some(rest) => {
+						let digits = rest
+							.find(|c: char| !c.is_ascii_digit())
+							.unwrap_or(rest.len());
+						match rest[..digits].parse() {
+							Ok(code) => (Some(code), &rest[digits..]),
+							Err(_) => continue,
+						}
+					}
+					None => (None, rest),
+				};
+
+				let Some(message) = rest.strip_prefix(':') else {
+					continue;
+				};
+
+				let location = if start == 0 { "" } else { &line[..start - 2] };
+				let (file, line, column) = Self::parse_location(location);
+
+				return Some(Diagnostic {
+					file,
+					line,
+					column,
+					severity,
+					code,
+					message: message.trim().to_string(),
+				});
+			}
+		}
+
+		None
+	}
+
+	/// Parses `file(line)` or `file(line, column)`.
+	fn parse_location(location: &str) -> (Option<String>, Option<u32>, Option<u32>) {
+		if location.is_empty() {
+			return (None, None, None);
+		}
+
+		let parsed = location.strip_suffix(')').and_then(|location| {
+			let (file, position) = location.rsplit_once('(')?;
+			let mut numbers = position.split(',').map(|n| n.trim().parse::<u32>());
+
+			let line = numbers.next()?.ok()?;
+			let column = numbers.next().transpose().ok()?;
+			Some((file, line, column))
+		});
+
+		match parsed {
+			Some((file, line, column)) => {
+				let file = (!file.is_empty()).then(|| file.to_string());
+				(file, Some(line), column)
+			}
+			None => (Some(location.to_string()), None, None),
+		}
+	}
+}
+
+/// Formats the diagnostic the same way Slang does, without the source excerpt.
+impl std::fmt::Display for Diagnostic {
+	fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+		match (&self.file, self.line, self.column) {
+			(file, Some(line), Some(column)) => {
+				write!(f, "{}({line}, {column}): ", file.as_deref().unwrap_or(""))?
+			}
+			(file, Some(line), None) => write!(f, "{}({line}): ", file.as_deref().unwrap_or(""))?,
+			(Some(file), None, _) => write!(f, "{file}: ")?,
+			(None, None, _) => {}
+		}
+
+		write!(f, "{}", self.severity)?;
+
+		if let Some(code) = self.code {
+			write!(f, " {code}")?;
+		}
+
+		write!(f, ": {}", self.message)
+	}
+}
+
+type Callback = Arc<dyn Fn(&Diagnostic) + Send + Sync>;
+
+struct Handler {
+	id: u64,
+	owner: usize,
+	callback: Callback,
+}
+
+static HANDLERS: Mutex<Vec<Handler>> = Mutex::new(Vec::new());
+static NEXT_HANDLER_ID: AtomicU64 = AtomicU64::new(0);
+
+// Mirrors the length of `HANDLERS`, so reporting doesn't take the lock while nothing is registered.
+static HANDLER_COUNT: AtomicUsize = AtomicUsize::new(0);
+
+/// Keeps a diagnostic callback registered, see [`crate::Session::on_diagnostic`].
+///
+/// The callback is removed when the handler is dropped.
+/// The handler keeps the session it was registered on alive.
+#[must_use = "the callback is removed when the handler is dropped"]
+pub struct DiagnosticHandler {
+	id: u64,
+	_owner: IUnknown,
+}
+
+impl Drop for DiagnosticHandler {
+	fn drop(&mut self) {
+		let mut handlers = HANDLERS.lock().unwrap();
+		handlers.retain(|handler| handler.id != self.id);
+		HANDLER_COUNT.store(handlers.len(), Ordering::Relaxed);
+	}
+}
+
+pub(crate) fn register(
+	owner: &IUnknown,
+	callback: impl Fn(&Diagnostic) + Send + Sync + 'static,
+) -> DiagnosticHandler {
+	let id = NEXT_HANDLER_ID.fetch_add(1, Ordering::Relaxed);
+
+	let mut handlers = HANDLERS.lock().unwrap();
+	handlers.push(Handler {
+		id,
+		owner: unsafe { owner.as_raw::<c_void>() } as usize,
+		callback: Arc::new(callback),
+	});
+	HANDLER_COUNT.store(handlers.len(), Ordering::Relaxed);
+	drop(handlers);
+
+	DiagnosticHandler {
+		id,
+		_owner: owner.clone(),
+	}
+}
+
+/// Passes `diagnostics` to the callbacks registered on `session` and on its global session.
+///
+/// # Safety
+///
+/// `session` must be null or a valid session and `diagnostics` must be null or a valid blob.
+pub(crate) unsafe fn report(session: *mut sys::slang_ISession, diagnostics: *mut sys::slang_IBlob) {
+	let Some(diagnostics) = std::ptr::NonNull::new(diagnostics as *mut c_void) else {
+		return;
+	};
+
+	if HANDLER_COUNT.load(Ordering::Relaxed) == 0 {
+		return;
+	}
+
+	let callbacks = {
+		let handlers = HANDLERS.lock().unwrap();
+
+		let global_session = match std::ptr::NonNull::new(session as *mut c_void) {
+			Some(session) => {
+				// Borrowed from the caller, so the reference must not be released.
+				let session = ManuallyDrop::new(Session(IUnknown(session)));
+				unsafe { (session.vtable().getGlobalSession)(session.as_raw()) }
+			}
+			None => std::ptr::null_mut(),
+		};
+		let owners = [session as usize, global_session as usize];
+
+		handlers
+			.iter()
+			.filter(|handler| owners.contains(&handler.owner))
+			.map(|handler| handler.callback.clone())
+			.collect::<Vec<_>>()
+	};
+
+	if callbacks.is_empty() {
+		return;
+	}
+
+	// Callbacks run without holding the lock, so they may register or drop handlers themselves.
+	let diagnostics = ManuallyDrop::new(Blob(IUnknown(diagnostics)));
+
+	for diagnostic in Diagnostic::parse_blob(&diagnostics) {
+		for callback in &callbacks {
+			callback(&diagnostic);
+		}
+	}
+}
+
+/// Forwards a diagnostic to the [`log`] crate, for use with [`crate::Session::on_diagnostic`].
+#[cfg(feature = "log")]
+pub fn log_diagnostic(diagnostic: &Diagnostic) {
+	let level = match diagnostic.severity {
+		Severity::Note => log::Level::Info,
+		Severity::Warning => log::Level::Warn,
+		Severity::Error | Severity::Fatal | Severity::Internal => log::Level::Error,
+	};
+
+	log::log!(target: "slang", level, "{diagnostic}");
+}
+
+/// Forwards a diagnostic to the [`tracing`] crate, for use with [`crate::Session::on_diagnostic`].
+#[cfg(feature = "tracing")]
+pub fn trace_diagnostic(diagnostic: &Diagnostic) {
+	match diagnostic.severity {
+		Severity::Note => tracing::info!(target: "slang", "{diagnostic}"),
+		Severity::Warning => tracing::warn!(target: "slang", "{diagnostic}"),
+		Severity::Error | Severity::Fatal | Severity::Internal => {
+			tracing::error!(target: "slang", "{diagnostic}")
+		}
+	}
+}
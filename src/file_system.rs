@@ -0,0 +1,304 @@
+use std::collections::BTreeMap;
+use std::ffi::{CStr, c_char, c_void};
+use std::panic::{AssertUnwindSafe, catch_unwind};
+use std::sync::{Arc, RwLock};
+
+use crate::com::{self, Implements, Object};
+use crate::{IUnknown, Interface, PathType, UUID, sys, uuid};
+
+/// A file system that Slang resolves module loads, `import`s and `#include`s through.
+///
+/// Attach it to a session with [`VirtualFileSystem::new`] and [`crate::SessionDesc::file_system`].
+pub trait FileSystem: Send + Sync {
+	fn load_file(&self, path: &str) -> std::io::Result<Vec<u8>>;
+
+	/// The default implementation reports every loadable path as a file.
+	fn path_type(&self, path: &str) -> std::io::Result<PathType> {
+		self.load_file(path).map(|_| PathType::File)
+	}
+
+	/// Returns a path that uniquely identifies the file,
+	/// which Slang uses to detect that two paths refer to the same module.
+	/// The default implementation resolves `.` and `..` components.
+	fn canonical_path(&self, path: &str) -> std::io::Result<String> {
+		Ok(simplify_path(path))
+	}
+}
+
+/// A [`FileSystem`] that keeps files in memory, keyed by their simplified path.
+///
+/// Clones share the same files, so files can still be changed
+/// after the file system was attached to a session.
+#[derive(Clone, Default)]
+pub struct MemoryFileSystem {
+	files: Arc<RwLock<BTreeMap<String, Vec<u8>>>>,
+}
+
+impl MemoryFileSystem {
+	pub fn new() -> Self {
+		Self::default()
+	}
+
+	pub fn with_file(self, path: &str, contents: impl Into<Vec<u8>>) -> Self {
+		self.insert(path, contents);
+		self
+	}
+
+	/// Adds or replaces a file, returning the previous contents.
+	pub fn insert(&self, path: &str, contents: impl Into<Vec<u8>>) -> Option<Vec<u8>> {
+		let mut files = self.files.write().unwrap();
+		files.insert(simplify_path(path), contents.into())
+	}
+
+	pub fn remove(&self, path: &str) -> Option<Vec<u8>> {
+		let mut files = self.files.write().unwrap();
+		files.remove(&simplify_path(path))
+	}
+
+	pub fn contains(&self, path: &str) -> bool {
+		let files = self.files.read().unwrap();
+		files.contains_key(&simplify_path(path))
+	}
+
+	/// Returns the paths of all files in sorted order.
+	pub fn paths(&self) -> Vec<String> {
+		let files = self.files.read().unwrap();
+		files.keys().cloned().collect()
+	}
+}
+
+impl FileSystem for MemoryFileSystem {
+	fn load_file(&self, path: &str) -> std::io::Result<Vec<u8>> {
+		let files = self.files.read().unwrap();
+		let file = files.get(&simplify_path(path));
+		file.cloned()
+			.ok_or_else(|| std::io::ErrorKind::NotFound.into())
+	}
+
+	fn path_type(&self, path: &str) -> std::io::Result<PathType> {
+		let path = simplify_path(path);
+		let files = self.files.read().unwrap();
+
+		if files.contains_key(&path) {
+			return Ok(PathType::File);
+		}
+
+		let prefix = if path == "." {
+			String::new()
+		} else {
+			format!("{path}/")
+		};
+
+		if files.keys().any(|file| file.starts_with(&prefix)) {
+			Ok(PathType::Directory)
+		} else {
+			Err(std::io::ErrorKind::NotFound.into())
+		}
+	}
+}
+
+/// A [`FileSystem`] wrapped in a Slang `ISlangFileSystemExt` object.
+#[repr(transparent)]
+#[derive(Clone)]
+pub struct VirtualFileSystem(IUnknown);
+
+unsafe impl Interface for VirtualFileSystem {
+	type Vtable = sys::IFileSystemExtVtable;
+	const IID: UUID = uuid(0x5fb632d2_979d_4481_9fee663c3f1449e1);
+}
+
+// The object is reference counted atomically and wraps a `FileSystem`, which is `Send + Sync`.
+unsafe impl Send for VirtualFileSystem {}
+unsafe impl Sync for VirtualFileSystem {}
+
+impl VirtualFileSystem {
+	pub fn new(file_system: impl FileSystem + 'static) -> VirtualFileSystem {
+		let object = FileSystemObject(Box::new(file_system));
+		VirtualFileSystem(Object::allocate(&FILE_SYSTEM_VTABLE, object))
+	}
+}
+
+const CASTABLE_IID: UUID = uuid(0x87ede0e1_4852_44b0_8bf2cb31874de239);
+const FILE_SYSTEM_IID: UUID = uuid(0x003a09fc_3a4d_4ba0_ad601fd863a915ab);
+
+struct FileSystemObject(Box<dyn FileSystem>);
+
+unsafe impl Implements for FileSystemObject {
+	const INTERFACES: &'static [UUID] = &[
+		IUnknown::IID,
+		CASTABLE_IID,
+		FILE_SYSTEM_IID,
+		VirtualFileSystem::IID,
+	];
+}
+
+static FILE_SYSTEM_VTABLE: sys::IFileSystemExtVtable = sys::IFileSystemExtVtable {
+	_base: sys::IFileSystemVtable {
+		_base: com::castable_vtable::<FileSystemObject>(),
+		loadFile: load_file,
+	},
+	getFileUniqueIdentity: get_file_unique_identity,
+	calcCombinedPath: calc_combined_path,
+	getPathType: get_path_type,
+	getPath: get_path,
+	clearCache: clear_cache,
+	enumeratePathContents: enumerate_path_contents,
+	getOSPathKind: get_os_path_kind,
+};
+
+/// Resolves `.` and `..` components and normalizes separators to `/`.
+pub(crate) fn simplify_path(path: &str) -> String {
+	let mut parts = Vec::new();
+
+	for part in path.split(['/', '\\']) {
+		match part {
+			"" | "." => {}
+			".." if parts.last().is_some_and(|last| *last != "..") => {
+				parts.pop();
+			}
+			_ => parts.push(part),
+		}
+	}
+
+	let simplified = parts.join("/");
+
+	if path.starts_with(['/', '\\']) {
+		format!("/{simplified}")
+	} else if simplified.is_empty() {
+		String::from(".")
+	} else {
+		simplified
+	}
+}
+
+fn result_from_io_error(error: &std::io::Error) -> sys::SlangResult {
+	match error.kind() {
+		std::io::ErrorKind::NotFound => crate::E_NOT_FOUND,
+		std::io::ErrorKind::PermissionDenied => crate::E_CANNOT_OPEN,
+		std::io::ErrorKind::InvalidInput => crate::E_INVALID_ARG,
+		std::io::ErrorKind::Unsupported => crate::E_NOT_IMPLEMENTED,
+		_ => crate::E_FAIL,
+	}
+}
+
+/// Calls into the user's file system, turning errors and panics into result codes.
+unsafe fn with_file_system<T>(
+	this: *mut c_void,
+	path: *const c_char,
+	f: impl FnOnce(&dyn FileSystem, &str) -> std::io::Result<T>,
+) -> Result<T, sys::SlangResult> {
+	let file_system = unsafe { Object::<FileSystemObject>::value(this) };
+	let path = unsafe { CStr::from_ptr(path) }.to_string_lossy();
+
+	match catch_unwind(AssertUnwindSafe(|| f(&*file_system.0, &path))) {
+		Ok(Ok(value)) => Ok(value),
+		Ok(Err(error)) => Err(result_from_io_error(&error)),
+		Err(_) => Err(crate::E_FAIL),
+	}
+}
+
+unsafe fn write_blob(
+	value: Result<Vec<u8>, sys::SlangResult>,
+	out_blob: *mut *mut sys::ISlangBlob,
+) -> sys::SlangResult {
+	match value {
+		Ok(data) => {
+			unsafe { *out_blob = com::into_raw(com::blob_from_vec(data)) };
+			crate::OK
+		}
+		Err(code) => code,
+	}
+}
+
+unsafe extern "C" fn load_file(
+	this: *mut c_void,
+	path: *const c_char,
+	out_blob: *mut *mut sys::ISlangBlob,
+) -> sys::SlangResult {
+	let data = unsafe { with_file_system(this, path, |fs, path| fs.load_file(path)) };
+	unsafe { write_blob(data, out_blob) }
+}
+
+unsafe extern "C" fn get_file_unique_identity(
+	this: *mut c_void,
+	path: *const c_char,
+	out_unique_identity: *mut *mut sys::ISlangBlob,
+) -> sys::SlangResult {
+	let identity = unsafe { with_file_system(this, path, |fs, path| fs.canonical_path(path)) };
+	unsafe { write_blob(identity.map(String::into_bytes), out_unique_identity) }
+}
+
+unsafe extern "C" fn calc_combined_path(
+	_this: *mut c_void,
+	from_path_type: PathType,
+	from_path: *const c_char,
+	path: *const c_char,
+	path_out: *mut *mut sys::ISlangBlob,
+) -> sys::SlangResult {
+	let from_path = unsafe { CStr::from_ptr(from_path) }.to_string_lossy();
+	let path = unsafe { CStr::from_ptr(path) }.to_string_lossy();
+
+	let directory = match from_path_type {
+		PathType::Directory => &*from_path,
+		PathType::File => from_path.rfind(['/', '\\']).map_or("", |i| &from_path[..i]),
+	};
+
+	let combined = if directory.is_empty() || path.starts_with(['/', '\\']) {
+		path.into_owned()
+	} else {
+		format!("{directory}/{path}")
+	};
+
+	unsafe { write_blob(Ok(simplify_path(&combined).into_bytes()), path_out) }
+}
+
+unsafe extern "C" fn get_path_type(
+	this: *mut c_void,
+	path: *const c_char,
+	path_type_out: *mut PathType,
+) -> sys::SlangResult {
+	match unsafe { with_file_system(this, path, |fs, path| fs.path_type(path)) } {
+		Ok(path_type) => {
+			unsafe { *path_type_out = path_type };
+			crate::OK
+		}
+		Err(code) => code,
+	}
+}
+
+unsafe extern "C" fn get_path(
+	this: *mut c_void,
+	kind: sys::PathKind,
+	path: *const c_char,
+	out_path: *mut *mut sys::ISlangBlob,
+) -> sys::SlangResult {
+	let result = match kind {
+		sys::PathKind::Simplified | sys::PathKind::Display => {
+			let path = unsafe { CStr::from_ptr(path) }.to_string_lossy();
+			Ok(simplify_path(&path))
+		}
+		sys::PathKind::Canonical => unsafe {
+			with_file_system(this, path, |fs, path| fs.canonical_path(path))
+		},
+		_ => Err(crate::E_NOT_IMPLEMENTED),
+	};
+
+	unsafe { write_blob(result.map(String::into_bytes), out_path) }
+}
+
+unsafe extern "C" fn clear_cache(_this: *mut c_void) {}
+
+unsafe extern "C" fn enumerate_path_contents(
+	_this: *mut c_void,
+	_path: *const c_char,
+	_callback: Option<
+		unsafe extern "C" fn(path_type: PathType, name: *const c_char, user_data: *mut c_void),
+	>,
+	_user_data: *mut c_void,
+) -> sys::SlangResult {
+	crate::E_NOT_IMPLEMENTED
+}
+
+unsafe extern "C" fn get_os_path_kind(_this: *mut c_void) -> sys::OSPathKind {
+	sys::OSPathKind::None
+}
@@ -2,22 +2,41 @@
 
 pub mod reflection;
 
+mod com;
+mod config;
+mod diagnostics;
+mod file_system;
+mod session_pool;
+
 #[cfg(test)]
 mod tests;
 
-use std::ffi::{CStr, CString};
+use std::ffi::{CStr, CString, c_char, c_void};
 use std::marker::PhantomData;
+use std::path::Path;
 use std::ptr::{null, null_mut};
 
 pub(crate) use shader_slang_sys as sys;
 
+pub use config::{AsSessionDesc, SessionConfig, TargetConfig};
+#[cfg(feature = "log")]
+pub use diagnostics::log_diagnostic;
+#[cfg(feature = "tracing")]
+pub use diagnostics::trace_diagnostic;
+pub use diagnostics::{Diagnostic, DiagnosticHandler, Severity};
+pub use file_system::{FileSystem, MemoryFileSystem, VirtualFileSystem};
+pub use session_pool::{JobHandle, SessionPool};
+
 pub use sys::{
-	SlangBindingType as BindingType, SlangCompileTarget as CompileTarget,
+	SlangArchiveType as ArchiveType, SlangBindingType as BindingType,
+	SlangCompileTarget as CompileTarget, SlangDebugInfoFormat as DebugInfoFormat,
 	SlangDebugInfoLevel as DebugInfoLevel, SlangDeclKind as DeclKind,
-	SlangFloatingPointMode as FloatingPointMode, SlangImageFormat as ImageFormat,
+	SlangEmitSpirvMethod as EmitSpirvMethod, SlangFloatingPointMode as FloatingPointMode,
+	SlangFpDenormalMode as FpDenormalMode, SlangImageFormat as ImageFormat,
 	SlangLayoutRules as LayoutRules, SlangLineDirectiveMode as LineDirectiveMode,
 	SlangMatrixLayoutMode as MatrixLayoutMode, SlangModifierID as ModifierID,
 	SlangOptimizationLevel as OptimizationLevel, SlangParameterCategory as ParameterCategory,
+	SlangPassThrough as PassThrough, SlangPathType as PathType,
 	SlangReflectionGenericArg as GenericArg, SlangReflectionGenericArgType as GenericArgType,
 	SlangResourceAccess as ResourceAccess, SlangResourceShape as ResourceShape,
 	SlangScalarType as ScalarType, SlangSourceLanguage as SourceLanguage, SlangStage as Stage,
@@ -41,15 +60,36 @@ const fn uuid(uuid: u128) -> UUID {
 }
 
 pub enum Error {
-	Code(sys::SlangResult),
+	Code(ErrorCode),
 	Blob(Blob),
+	/// Slang reported success, or failed without diagnostics, but returned no object.
+	Null,
+	/// Slang returned data of a different size than the wrapper expects.
+	UnexpectedSize {
+		expected: usize,
+		actual: usize,
+	},
+}
+
+impl Error {
+	/// Parses the diagnostics carried by [`Error::Blob`].
+	pub fn diagnostics(&self) -> Vec<Diagnostic> {
+		match self {
+			Error::Code(_) | Error::Null | Error::UnexpectedSize { .. } => Vec::new(),
+			Error::Blob(blob) => Diagnostic::parse_blob(blob),
+		}
+	}
 }
 
 impl std::fmt::Debug for Error {
 	fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
 		match self {
-			Error::Code(code) => write!(f, "{}", code),
+			Error::Code(code) => write!(f, "{:?}", code),
 			Error::Blob(blob) => write!(f, "{}", blob.as_str().unwrap_or_default()),
+			Error::Null => write!(f, "Null"),
+			Error::UnexpectedSize { expected, actual } => {
+				write!(f, "expected {expected} bytes, got {actual}")
+			}
 		}
 	}
 }
@@ -66,19 +106,179 @@ impl std::error::Error for Error {}
 
 pub type Result<T> = std::result::Result<T, Error>;
 
+// Result codes from slang.h, which are defined through function-like macros that bindgen can't evaluate.
+pub(crate) const OK: sys::SlangResult = 0;
+pub(crate) const E_FAIL: sys::SlangResult = 0x80004005_u32 as _;
+pub(crate) const E_NOT_IMPLEMENTED: sys::SlangResult = 0x80004001_u32 as _;
+pub(crate) const E_NO_INTERFACE: sys::SlangResult = 0x80004002_u32 as _;
+pub(crate) const E_ABORT: sys::SlangResult = 0x80004004_u32 as _;
+pub(crate) const E_INVALID_ARG: sys::SlangResult = 0x80070057_u32 as _;
+pub(crate) const E_OUT_OF_MEMORY: sys::SlangResult = 0x8007000e_u32 as _;
+pub(crate) const E_BUFFER_TOO_SMALL: sys::SlangResult = 0x82000001_u32 as _;
+pub(crate) const E_UNINITIALIZED: sys::SlangResult = 0x82000002_u32 as _;
+pub(crate) const E_PENDING: sys::SlangResult = 0x82000003_u32 as _;
+pub(crate) const E_CANNOT_OPEN: sys::SlangResult = 0x82000004_u32 as _;
+pub(crate) const E_NOT_FOUND: sys::SlangResult = 0x82000005_u32 as _;
+pub(crate) const E_INTERNAL_FAIL: sys::SlangResult = 0x82000006_u32 as _;
+pub(crate) const E_NOT_AVAILABLE: sys::SlangResult = 0x82000007_u32 as _;
+pub(crate) const E_TIME_OUT: sys::SlangResult = 0x82000008_u32 as _;
+
+/// Failure codes returned by Slang, named after the `SLANG_E_*` macros in slang.h.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+pub enum ErrorCode {
+	Fail,
+	NotImplemented,
+	NoInterface,
+	Abort,
+	InvalidArg,
+	OutOfMemory,
+	BufferTooSmall,
+	Uninitialized,
+	Pending,
+	CannotOpen,
+	NotFound,
+	InternalFail,
+	NotAvailable,
+	TimeOut,
+	Other(sys::SlangResult),
+}
+
+impl ErrorCode {
+	pub fn from_raw(code: sys::SlangResult) -> ErrorCode {
+		match code {
+			E_FAIL => ErrorCode::Fail,
+			E_NOT_IMPLEMENTED => ErrorCode::NotImplemented,
+			E_NO_INTERFACE => ErrorCode::NoInterface,
+			E_ABORT => ErrorCode::Abort,
+			E_INVALID_ARG => ErrorCode::InvalidArg,
+			E_OUT_OF_MEMORY => ErrorCode::OutOfMemory,
+			E_BUFFER_TOO_SMALL => ErrorCode::BufferTooSmall,
+			E_UNINITIALIZED => ErrorCode::Uninitialized,
+			E_PENDING => ErrorCode::Pending,
+			E_CANNOT_OPEN => ErrorCode::CannotOpen,
+			E_NOT_FOUND => ErrorCode::NotFound,
+			E_INTERNAL_FAIL => ErrorCode::InternalFail,
+			E_NOT_AVAILABLE => ErrorCode::NotAvailable,
+			E_TIME_OUT => ErrorCode::TimeOut,
+			code => ErrorCode::Other(code),
+		}
+	}
+
+	pub fn raw(self) -> sys::SlangResult {
+		match self {
+			ErrorCode::Fail => E_FAIL,
+			ErrorCode::NotImplemented => E_NOT_IMPLEMENTED,
+			ErrorCode::NoInterface => E_NO_INTERFACE,
+			ErrorCode::Abort => E_ABORT,
+			ErrorCode::InvalidArg => E_INVALID_ARG,
+			ErrorCode::OutOfMemory => E_OUT_OF_MEMORY,
+			ErrorCode::BufferTooSmall => E_BUFFER_TOO_SMALL,
+			ErrorCode::Uninitialized => E_UNINITIALIZED,
+			ErrorCode::Pending => E_PENDING,
+			ErrorCode::CannotOpen => E_CANNOT_OPEN,
+			ErrorCode::NotFound => E_NOT_FOUND,
+			ErrorCode::InternalFail => E_INTERNAL_FAIL,
+			ErrorCode::NotAvailable => E_NOT_AVAILABLE,
+			ErrorCode::TimeOut => E_TIME_OUT,
+			ErrorCode::Other(code) => code,
+		}
+	}
+}
+
+impl From<sys::SlangResult> for ErrorCode {
+	fn from(value: sys::SlangResult) -> Self {
+		ErrorCode::from_raw(value)
+	}
+}
+
 pub(crate) fn succeeded(result: sys::SlangResult) -> bool {
 	result >= 0
 }
 
+fn c_string(string: &str) -> Result<CString> {
+	CString::new(string).map_err(|_| Error::Code(ErrorCode::InvalidArg))
+}
+
+/// Borrows a string returned by Slang, which is `None` if it is null or not valid UTF-8.
+fn str_from_ptr<'a>(ptr: *const c_char) -> Option<&'a str> {
+	(!ptr.is_null())
+		.then(|| unsafe { CStr::from_ptr(ptr) }.to_str().ok())
+		.flatten()
+}
+
+/// Wraps an object returned by Slang, failing with [`Error::Null`] if it is missing.
+pub(crate) fn unknown_from_raw<T>(ptr: *mut T) -> Result<IUnknown> {
+	std::ptr::NonNull::new(ptr as *mut c_void)
+		.map(IUnknown)
+		.ok_or(Error::Null)
+}
+
+fn blob_from_raw(blob: *mut sys::slang_IBlob) -> Option<Blob> {
+	Some(Blob(IUnknown(std::ptr::NonNull::new(blob as *mut _)?)))
+}
+
 fn result_from_blob(code: sys::SlangResult, blob: *mut sys::slang_IBlob) -> Result<()> {
-	if code < 0 && !blob.is_null() {
-		Err(Error::Blob(Blob(IUnknown(
-			std::ptr::NonNull::new(blob as *mut _).unwrap(),
-		))))
-	} else if code < 0 {
-		Err(Error::Code(code))
+	result_with_diagnostics(code, blob).map(|_| ())
+}
+
+/// Like [`result_from_blob`], but keeps the diagnostics of a successful call.
+fn result_with_diagnostics(
+	code: sys::SlangResult,
+	blob: *mut sys::slang_IBlob,
+) -> Result<Option<Blob>> {
+	let blob = blob_from_raw(blob);
+
+	if code < 0 {
+		Err(blob.map_or(Error::Code(code.into()), Error::Blob))
 	} else {
-		Ok(())
+		Ok(blob)
+	}
+}
+
+/// The result of a successful call, together with the diagnostics (typically warnings) it emitted.
+pub struct WithDiagnostics<T> {
+	pub value: T,
+	pub diagnostics: Option<Blob>,
+}
+
+impl<T> WithDiagnostics<T> {
+	pub fn parsed_diagnostics(&self) -> Vec<Diagnostic> {
+		self.diagnostics
+			.as_ref()
+			.map_or_else(Vec::new, Diagnostic::parse_blob)
+	}
+}
+
+/// Wraps a module returned by one of the `loadModule*` functions, which don't add a reference.
+fn module_with_diagnostics<'a>(
+	module: *mut sys::slang_IModule,
+	diagnostics: *mut sys::slang_IBlob,
+) -> Result<WithDiagnostics<Module<'a>>> {
+	let diagnostics = blob_from_raw(diagnostics);
+
+	let Ok(module) = unknown_from_raw(module) else {
+		return Err(diagnostics.map_or(Error::Null, Error::Blob));
+	};
+
+	let module = Module(module, PhantomData);
+	unsafe { (module.as_unknown().vtable().ISlangUnknown_addRef)(module.as_raw()) };
+
+	Ok(WithDiagnostics {
+		value: module,
+		diagnostics,
+	})
+}
+
+fn global_session_from_raw(
+	result: sys::SlangResult,
+	global_session: *mut sys::slang_IGlobalSession,
+) -> Result<GlobalSession> {
+	let global_session = unknown_from_raw(global_session);
+
+	if succeeded(result) {
+		Ok(GlobalSession(global_session?))
+	} else {
+		Err(Error::Code(result.into()))
 	}
 }
 
@@ -159,6 +359,14 @@ unsafe impl Interface for Blob {
 }
 
 impl Blob {
+	pub fn from_vec(data: Vec<u8>) -> Blob {
+		com::blob_from_vec(data)
+	}
+
+	pub fn from_bytes(data: &[u8]) -> Blob {
+		com::blob_from_vec(data.to_vec())
+	}
+
 	pub fn as_slice(&self) -> &[u8] {
 		let ptr = vcall!(self, getBufferPointer());
 		let size = vcall!(self, getBufferSize());
@@ -168,8 +376,134 @@ impl Blob {
 	pub fn as_str(&self) -> std::result::Result<&str, std::str::Utf8Error> {
 		std::str::from_utf8(self.as_slice())
 	}
+
+	/// Formats the contents as lowercase hexadecimal, as used for digests and hashes.
+	pub fn to_hex(&self) -> String {
+		use std::fmt::Write;
+
+		self.as_slice().iter().fold(
+			String::with_capacity(self.as_slice().len() * 2),
+			|mut hex, byte| {
+				let _ = write!(hex, "{byte:02x}");
+				hex
+			},
+		)
+	}
 }
 
+impl From<Vec<u8>> for Blob {
+	fn from(value: Vec<u8>) -> Self {
+		Blob::from_vec(value)
+	}
+}
+
+/// The outputs of a compile, returned by [`ComponentType::result_file_system`].
+#[repr(transparent)]
+#[derive(Clone)]
+pub struct ResultFileSystem(IUnknown);
+
+unsafe impl Interface for ResultFileSystem {
+	type Vtable = sys::IMutableFileSystemVtable;
+	const IID: UUID = uuid(0xa058675c_1d65_452a_8458ccded1427105);
+}
+
+impl ResultFileSystem {
+	pub fn load_file(&self, path: &str) -> Result<Blob> {
+		let path = c_string(path)?;
+		let mut blob = null_mut();
+
+		let result = unsafe {
+			(self.vtable()._base._base.loadFile)(self.as_raw(), path.as_ptr(), &mut blob)
+		};
+
+		if succeeded(result) {
+			Ok(Blob(unknown_from_raw(blob)?))
+		} else {
+			Err(Error::Code(result.into()))
+		}
+	}
+
+	pub fn read(&self, path: &str) -> Result<Vec<u8>> {
+		Ok(self.load_file(path)?.as_slice().to_vec())
+	}
+
+	pub fn path_type(&self, path: &str) -> Result<PathType> {
+		let path = c_string(path)?;
+		let mut path_type = PathType::File;
+
+		let result = unsafe {
+			(self.vtable()._base.getPathType)(self.as_raw(), path.as_ptr(), &mut path_type)
+		};
+
+		if succeeded(result) {
+			Ok(path_type)
+		} else {
+			Err(Error::Code(result.into()))
+		}
+	}
+
+	/// Lists the direct children of the directory at `path` by name.
+	pub fn path_contents(&self, path: &str) -> Result<Vec<(PathType, String)>> {
+		unsafe extern "C" fn push_entry(
+			path_type: PathType,
+			name: *const c_char,
+			user_data: *mut c_void,
+		) {
+			let contents = unsafe { &mut *(user_data as *mut Vec<(PathType, String)>) };
+			let name = unsafe { CStr::from_ptr(name) };
+			contents.push((path_type, name.to_string_lossy().into_owned()));
+		}
+
+		let path = c_string(path)?;
+		let mut contents = Vec::new();
+
+		let result = unsafe {
+			(self.vtable()._base.enumeratePathContents)(
+				self.as_raw(),
+				path.as_ptr(),
+				Some(push_entry),
+				&mut contents as *mut Vec<(PathType, String)> as *mut c_void,
+			)
+		};
+
+		if succeeded(result) {
+			Ok(contents)
+		} else {
+			Err(Error::Code(result.into()))
+		}
+	}
+
+	/// Recursively collects the paths of all files, starting at the root directory.
+	pub fn files(&self) -> Result<Vec<String>> {
+		let mut files = Vec::new();
+		let mut directories = vec![String::from(".")];
+
+		while let Some(directory) = directories.pop() {
+			for (path_type, name) in self.path_contents(&directory)? {
+				let path = if directory == "." {
+					name
+				} else {
+					format!("{directory}/{name}")
+				};
+
+				match path_type {
+					PathType::File => files.push(path),
+					PathType::Directory => directories.push(path),
+				}
+			}
+		}
+
+		Ok(files)
+	}
+}
+
+/// Global sessions are not thread-safe either, so they can't be sent to or shared with
+/// other threads. Every [`SessionPool`] worker creates its own.
+///
+/// ```compile_fail
+/// fn assert_send<T: Send>() {}
+/// assert_send::<shader_slang::GlobalSession>();
+/// ```
 #[repr(transparent)]
 #[derive(Clone)]
 pub struct GlobalSession(IUnknown);
@@ -180,51 +514,129 @@ unsafe impl Interface for GlobalSession {
 }
 
 impl GlobalSession {
-	pub fn new() -> Option<GlobalSession> {
+	pub fn new() -> Result<GlobalSession> {
 		let mut global_session = null_mut();
-		unsafe { sys::slang_createGlobalSession(sys::SLANG_API_VERSION as _, &mut global_session) };
-		Some(GlobalSession(IUnknown(std::ptr::NonNull::new(
-			global_session as *mut _,
-		)?)))
+		let result = unsafe {
+			sys::slang_createGlobalSession(sys::SLANG_API_VERSION as _, &mut global_session)
+		};
+		global_session_from_raw(result, global_session)
 	}
 
-	pub fn new_without_core_module() -> Option<GlobalSession> {
+	pub fn new_without_core_module() -> Result<GlobalSession> {
 		let mut global_session = null_mut();
-		unsafe {
+		let result = unsafe {
 			sys::slang_createGlobalSessionWithoutCoreModule(
 				sys::SLANG_API_VERSION as _,
 				&mut global_session,
 			)
 		};
-		Some(GlobalSession(IUnknown(std::ptr::NonNull::new(
-			global_session as *mut _,
-		)?)))
+		global_session_from_raw(result, global_session)
 	}
 
-	pub fn create_session(&self, desc: &SessionDesc) -> Option<Session> {
+	/// Creates a session from a borrowed [`SessionDesc`] or an owned [`SessionConfig`].
+	pub fn create_session(&self, desc: &impl AsSessionDesc) -> Result<Session> {
 		let mut session = null_mut();
-		vcall!(self, createSession(&**desc, &mut session));
-		Some(Session(IUnknown(std::ptr::NonNull::new(
-			session as *mut _,
-		)?)))
+		let result = desc.with_session_desc(|desc| {
+			desc.check_options()?;
+			Ok(vcall!(self, createSession(&**desc, &mut session)))
+		})?;
+
+		// Take ownership of the session before checking the result, so it is released either way.
+		let session = unknown_from_raw(session);
+
+		if succeeded(result) {
+			Ok(Session(session?))
+		} else {
+			Err(Error::Code(result.into()))
+		}
 	}
 
-	pub fn find_profile(&self, name: &str) -> ProfileID {
-		let name = CString::new(name).unwrap();
-		ProfileID(vcall!(self, findProfile(name.as_ptr())))
+	/// Parses `slangc` arguments, without the program name, into a session description
+	/// that can be passed to [`GlobalSession::create_session`].
+	pub fn parse_command_line(&self, args: &[&str]) -> Result<OwnedSessionDesc> {
+		let args = args
+			.iter()
+			.map(|arg| c_string(arg))
+			.collect::<Result<Vec<_>>>()?;
+		let arg_ptrs = args.iter().map(|arg| arg.as_ptr()).collect::<Vec<_>>();
+
+		let mut desc = SessionDesc::default();
+		let mut aux_allocation = null_mut();
+
+		let result = vcall!(
+			self,
+			parseCommandLineArguments(
+				arg_ptrs.len() as _,
+				arg_ptrs.as_ptr(),
+				&mut desc.inner,
+				&mut aux_allocation
+			)
+		);
+
+		// Take ownership of the allocation before checking the result, so it is released either way.
+		let aux_allocation = std::ptr::NonNull::new(aux_allocation as *mut c_void).map(IUnknown);
+
+		if succeeded(result) {
+			Ok(OwnedSessionDesc {
+				desc,
+				_aux_allocation: aux_allocation,
+			})
+		} else {
+			Err(Error::Code(result.into()))
+		}
+	}
+
+	/// Returns a digest of everything in the session description that affects compilation,
+	/// for use in cache keys. See [`Blob::to_hex`] to format it.
+	pub fn session_desc_digest(&self, desc: &impl AsSessionDesc) -> Result<Blob> {
+		let mut digest = null_mut();
+		let result = desc.with_session_desc(|desc| {
+			desc.check_options()?;
+			Ok(vcall!(self, getSessionDescDigest(&**desc, &mut digest)))
+		})?;
+
+		if succeeded(result) {
+			Ok(Blob(unknown_from_raw(digest)?))
+		} else {
+			Err(Error::Code(result.into()))
+		}
 	}
 
-	pub fn find_capability(&self, name: &str) -> CapabilityID {
-		let name = CString::new(name).unwrap();
-		CapabilityID(vcall!(self, findCapability(name.as_ptr())))
+	/// Returns [`ProfileID::UNKNOWN`] if Slang doesn't know the profile.
+	pub fn find_profile(&self, name: &str) -> Result<ProfileID> {
+		let name = c_string(name)?;
+		Ok(ProfileID(vcall!(self, findProfile(name.as_ptr()))))
 	}
 
-	pub fn build_tag_string(&self) -> &str {
-		let tag = vcall!(self, getBuildTagString());
-		unsafe { CStr::from_ptr(tag).to_str().unwrap() }
+	/// Returns [`CapabilityID::UNKNOWN`] if Slang doesn't know the capability.
+	pub fn find_capability(&self, name: &str) -> Result<CapabilityID> {
+		let name = c_string(name)?;
+		Ok(CapabilityID(vcall!(self, findCapability(name.as_ptr()))))
+	}
+
+	pub fn build_tag_string(&self) -> Option<&str> {
+		str_from_ptr(vcall!(self, getBuildTagString()))
+	}
+
+	/// Calls `callback` for the diagnostics returned to this crate's wrappers by any session
+	/// created from this global session, for as long as the returned handler is alive.
+	///
+	/// Diagnostics Slang only writes to its own diagnostic output are not reported.
+	pub fn on_diagnostic(
+		&self,
+		callback: impl Fn(&Diagnostic) + Send + Sync + 'static,
+	) -> DiagnosticHandler {
+		diagnostics::register(self.as_unknown(), callback)
 	}
 }
 
+/// Sessions are not thread-safe, and clones share the same non-atomic reference count,
+/// so they can't be sent to or shared with other threads. Use a [`SessionPool`] instead.
+///
+/// ```compile_fail
+/// fn assert_send<T: Send>() {}
+/// assert_send::<shader_slang::Session>();
+/// ```
 #[repr(transparent)]
 #[derive(Clone)]
 pub struct Session(IUnknown);
@@ -235,22 +647,19 @@ unsafe impl Interface for Session {
 }
 
 impl Session {
-	pub fn load_module(&self, name: &str) -> Result<Module> {
-		let name = CString::new(name).unwrap();
+	pub fn load_module(&self, name: &str) -> Result<Module<'_>> {
+		self.load_module_with_diagnostics(name)
+			.map(|result| result.value)
+	}
+
+	pub fn load_module_with_diagnostics(&self, name: &str) -> Result<WithDiagnostics<Module<'_>>> {
+		let name = c_string(name)?;
 		let mut diagnostics = null_mut();
 
 		let module = vcall!(self, loadModule(name.as_ptr(), &mut diagnostics));
 
-		if module.is_null() {
-			let blob = Blob(IUnknown(
-				std::ptr::NonNull::new(diagnostics as *mut _).unwrap(),
-			));
-			Err(Error::Blob(blob))
-		} else {
-			let module = Module(IUnknown(std::ptr::NonNull::new(module as *mut _).unwrap()));
-			unsafe { (module.as_unknown().vtable().ISlangUnknown_addRef)(module.as_raw()) };
-			Ok(module)
-		}
+		self.report_diagnostics(diagnostics);
+		module_with_diagnostics(module, diagnostics)
 	}
 
 	pub fn load_module_from_source_string(
@@ -258,10 +667,20 @@ impl Session {
 		module_name: &str,
 		path: &str,
 		source: &str,
-	) -> Result<Module> {
-		let module_name = CString::new(module_name).unwrap();
-		let path = CString::new(path).unwrap();
-		let source = CString::new(source).unwrap();
+	) -> Result<Module<'_>> {
+		self.load_module_from_source_string_with_diagnostics(module_name, path, source)
+			.map(|result| result.value)
+	}
+
+	pub fn load_module_from_source_string_with_diagnostics(
+		&self,
+		module_name: &str,
+		path: &str,
+		source: &str,
+	) -> Result<WithDiagnostics<Module<'_>>> {
+		let module_name = c_string(module_name)?;
+		let path = c_string(path)?;
+		let source = c_string(source)?;
 		let mut diagnostics = null_mut();
 
 		let module = vcall!(
@@ -274,16 +693,52 @@ impl Session {
 			)
 		);
 
-		if module.is_null() {
-			let blob = Blob(IUnknown(
-				std::ptr::NonNull::new(diagnostics as *mut _).unwrap(),
-			));
-			Err(Error::Blob(blob))
-		} else {
-			let module = Module(IUnknown(std::ptr::NonNull::new(module as *mut _).unwrap()));
-			unsafe { (module.as_unknown().vtable().ISlangUnknown_addRef)(module.as_raw()) };
-			Ok(module)
-		}
+		self.report_diagnostics(diagnostics);
+		module_with_diagnostics(module, diagnostics)
+	}
+
+	pub fn load_module_from_source(
+		&self,
+		module_name: &str,
+		path: &str,
+		source: &Blob,
+	) -> Result<Module<'_>> {
+		self.load_module_from_source_with_diagnostics(module_name, path, source)
+			.map(|result| result.value)
+	}
+
+	pub fn load_module_from_source_with_diagnostics(
+		&self,
+		module_name: &str,
+		path: &str,
+		source: &Blob,
+	) -> Result<WithDiagnostics<Module<'_>>> {
+		let module_name = c_string(module_name)?;
+		let path = c_string(path)?;
+		let mut diagnostics = null_mut();
+
+		let module = vcall!(
+			self,
+			loadModuleFromSource(
+				module_name.as_ptr(),
+				path.as_ptr(),
+				source.as_raw(),
+				&mut diagnostics
+			)
+		);
+
+		self.report_diagnostics(diagnostics);
+		module_with_diagnostics(module, diagnostics)
+	}
+
+	/// Unlike [`Session::load_module_from_source_string`], the source may contain NUL bytes.
+	pub fn load_module_from_source_bytes(
+		&self,
+		module_name: &str,
+		path: &str,
+		source: &[u8],
+	) -> Result<Module<'_>> {
+		self.load_module_from_source(module_name, path, &Blob::from_bytes(source))
 	}
 
 	pub fn load_module_from_ir_blob(
@@ -291,9 +746,19 @@ impl Session {
 		module_name: &str,
 		path: &str,
 		ir_blob: &Blob,
-	) -> Result<Module> {
-		let module_name = CString::new(module_name).unwrap();
-		let path = CString::new(path).unwrap();
+	) -> Result<Module<'_>> {
+		self.load_module_from_ir_blob_with_diagnostics(module_name, path, ir_blob)
+			.map(|result| result.value)
+	}
+
+	pub fn load_module_from_ir_blob_with_diagnostics(
+		&self,
+		module_name: &str,
+		path: &str,
+		ir_blob: &Blob,
+	) -> Result<WithDiagnostics<Module<'_>>> {
+		let module_name = c_string(module_name)?;
+		let path = c_string(path)?;
 		let mut diagnostics = null_mut();
 
 		let module = vcall!(
@@ -306,41 +771,88 @@ impl Session {
 			)
 		);
 
-		if module.is_null() {
-			let blob = Blob(IUnknown(
-				std::ptr::NonNull::new(diagnostics as *mut _).unwrap(),
-			));
-			Err(Error::Blob(blob))
-		} else {
-			let module = Module(IUnknown(std::ptr::NonNull::new(module as *mut _).unwrap()));
-			unsafe { (module.as_unknown().vtable().ISlangUnknown_addRef)(module.as_raw()) };
-			Ok(module)
-		}
+		self.report_diagnostics(diagnostics);
+		module_with_diagnostics(module, diagnostics)
+	}
+
+	pub fn is_binary_module_up_to_date(
+		&self,
+		module_path: &str,
+		binary_module: &Blob,
+	) -> Result<bool> {
+		let module_path = c_string(module_path)?;
+		Ok(vcall!(
+			self,
+			isBinaryModuleUpToDate(module_path.as_ptr(), binary_module.as_raw())
+		))
+	}
+
+	pub fn loaded_module_count(&self) -> u32 {
+		vcall!(self, getLoadedModuleCount()) as _
+	}
+
+	pub fn loaded_module_by_index(&self, index: u32) -> Option<Module<'_>> {
+		let module = vcall!(self, getLoadedModule(index as _));
+		let module = Module(
+			IUnknown(std::ptr::NonNull::new(module as *mut _)?),
+			PhantomData,
+		);
+		unsafe { (module.as_unknown().vtable().ISlangUnknown_addRef)(module.as_raw()) };
+		Some(module)
+	}
+
+	/// Yields [`Error::Null`] for a module that Slang doesn't return.
+	pub fn loaded_modules(&self) -> impl ExactSizeIterator<Item = Result<Module<'_>>> {
+		(0..self.loaded_module_count()).map(|i| self.loaded_module_by_index(i).ok_or(Error::Null))
 	}
 
 	pub fn create_composite_component_type(
 		&self,
-		components: &[ComponentType],
-	) -> Result<ComponentType> {
+		components: &[ComponentType<'_>],
+	) -> Result<ComponentType<'_>> {
+		self.create_composite_component_type_with_diagnostics(components)
+			.map(|result| result.value)
+	}
+
+	pub fn create_composite_component_type_with_diagnostics(
+		&self,
+		components: &[ComponentType<'_>],
+	) -> Result<WithDiagnostics<ComponentType<'_>>> {
 		let mut composite_component_type = null_mut();
 		let mut diagnostics = null_mut();
 
-		result_from_blob(
-			vcall!(
-				self,
-				createCompositeComponentType(
-					components.as_ptr() as _,
-					components.len() as _,
-					&mut composite_component_type,
-					&mut diagnostics
-				)
-			),
+		let result = vcall!(
+			self,
+			createCompositeComponentType(
+				components.as_ptr() as _,
+				components.len() as _,
+				&mut composite_component_type,
+				&mut diagnostics
+			)
+		);
+		self.report_diagnostics(diagnostics);
+
+		let diagnostics = result_with_diagnostics(result, diagnostics)?;
+
+		Ok(WithDiagnostics {
+			value: ComponentType(unknown_from_raw(composite_component_type)?, PhantomData),
 			diagnostics,
-		)?;
+		})
+	}
+
+	/// Calls `callback` for the diagnostics returned to this crate's wrappers by this session,
+	/// for as long as the returned handler is alive.
+	///
+	/// Diagnostics Slang only writes to its own diagnostic output are not reported.
+	pub fn on_diagnostic(
+		&self,
+		callback: impl Fn(&Diagnostic) + Send + Sync + 'static,
+	) -> DiagnosticHandler {
+		diagnostics::register(self.as_unknown(), callback)
+	}
 
-		Ok(ComponentType(IUnknown(
-			std::ptr::NonNull::new(composite_component_type as *mut _).unwrap(),
-		)))
+	fn report_diagnostics(&self, diagnostics: *mut sys::slang_IBlob) {
+		unsafe { diagnostics::report(self.as_raw(), diagnostics) };
 	}
 }
 
@@ -369,89 +881,341 @@ impl Metadata {
 	}
 }
 
+pub enum SpecializationArg<'a> {
+	Type(&'a reflection::Type),
+	Expr(&'a str),
+}
+
+/// Component types, modules and entry points borrow the [`Session`] they were created from,
+/// because Slang frees their reflection data together with the session.
+///
+/// Using them, or their reflection data, after the session is dropped doesn't compile:
+///
+/// ```compile_fail
+/// # let global_session = shader_slang::GlobalSession::new().unwrap();
+/// # let session_desc = shader_slang::SessionDesc::default();
+/// let session = global_session.create_session(&session_desc).unwrap();
+/// let module = session.load_module("shader.slang").unwrap();
+/// let reflection = module.module_reflection();
+///
+/// drop(session);
+/// reflection.name();
+/// ```
+///
+/// ```compile_fail
+/// # let global_session = shader_slang::GlobalSession::new().unwrap();
+/// # let session_desc = shader_slang::SessionDesc::default();
+/// let session = global_session.create_session(&session_desc).unwrap();
+/// let module = session.load_module("shader.slang").unwrap();
+/// let entry_point = module.find_entry_point_by_name("main").unwrap();
+///
+/// drop(session);
+/// entry_point.function_reflection().name();
+/// ```
+///
+/// This includes component types derived from them by linking or specializing, and the types
+/// specialized through their layout:
+///
+/// ```compile_fail
+/// # let global_session = shader_slang::GlobalSession::new().unwrap();
+/// # let session_desc = shader_slang::SessionDesc::default();
+/// let session = global_session.create_session(&session_desc).unwrap();
+/// let module = session.load_module("shader.slang").unwrap();
+/// let linked_program = shader_slang::ComponentType::from(module).link().unwrap();
+///
+/// drop(session);
+/// linked_program.layout(0).unwrap().parameter_count();
+/// ```
+///
+/// ```compile_fail
+/// # let global_session = shader_slang::GlobalSession::new().unwrap();
+/// # let session_desc = shader_slang::SessionDesc::default();
+/// let session = global_session.create_session(&session_desc).unwrap();
+/// let module = session.load_module("shader.slang").unwrap();
+/// let linked_program = shader_slang::ComponentType::from(module).link().unwrap();
+/// let reflection = linked_program.layout(0).unwrap();
+///
+/// let generic = reflection.find_type_by_name("Buffer").unwrap();
+/// let float = reflection.find_type_by_name("float").unwrap();
+/// let specialized = reflection.specialize_type(generic, &[float]).unwrap();
+///
+/// drop(session);
+/// specialized.name();
+/// ```
+///
+/// Like the [`Session`] they belong to, component types can't be sent to other threads:
+///
+/// ```compile_fail
+/// fn assert_send<T: Send>() {}
+/// assert_send::<shader_slang::ComponentType>();
+/// ```
 #[repr(transparent)]
 #[derive(Clone)]
-pub struct ComponentType(IUnknown);
+pub struct ComponentType<'a>(IUnknown, PhantomData<&'a Session>);
 
-unsafe impl Interface for ComponentType {
+unsafe impl Interface for ComponentType<'_> {
 	type Vtable = sys::IComponentTypeVtable;
 	const IID: UUID = uuid(0x5bc42be8_5c50_4929_9e5ed15e7c24015f);
 }
 
-impl ComponentType {
+impl<'a> ComponentType<'a> {
 	pub fn layout(&self, target: i64) -> Result<&reflection::Shader> {
+		self.layout_with_diagnostics(target)
+			.map(|result| result.value)
+	}
+
+	pub fn layout_with_diagnostics(
+		&self,
+		target: i64,
+	) -> Result<WithDiagnostics<&reflection::Shader>> {
 		let mut diagnostics = null_mut();
 		let ptr = vcall!(self, getLayout(target, &mut diagnostics));
+		self.report_diagnostics(diagnostics);
+
+		// Take ownership of the diagnostics in every path, so they are released.
+		let diagnostics = blob_from_raw(diagnostics);
 
 		if ptr.is_null() {
-			Err(Error::Blob(Blob(IUnknown(
-				std::ptr::NonNull::new(diagnostics as *mut _).unwrap(),
-			))))
+			Err(diagnostics.map_or(Error::Null, Error::Blob))
 		} else {
-			Ok(unsafe { &*(ptr as *const _) })
+			Ok(WithDiagnostics {
+				value: unsafe { &*(ptr as *const _) },
+				diagnostics,
+			})
 		}
 	}
 
-	pub fn link(&self) -> Result<ComponentType> {
+	pub fn specialization_param_count(&self) -> i64 {
+		vcall!(self, getSpecializationParamCount())
+	}
+
+	pub fn specialize(&self, args: &[SpecializationArg]) -> Result<ComponentType<'a>> {
+		self.specialize_with_diagnostics(args)
+			.map(|result| result.value)
+	}
+
+	pub fn specialize_with_diagnostics(
+		&self,
+		args: &[SpecializationArg],
+	) -> Result<WithDiagnostics<ComponentType<'a>>> {
+		let mut strings = Vec::new();
+
+		let args = args
+			.iter()
+			.map(|arg| match arg {
+				SpecializationArg::Type(ty) => Ok(sys::slang_SpecializationArg {
+					kind: sys::slang_SpecializationArg_Kind::Type,
+					__bindgen_anon_1: sys::slang_SpecializationArg__bindgen_ty_1 {
+						type_: *ty as *const _ as *mut _,
+					},
+				}),
+				SpecializationArg::Expr(expr) => {
+					let expr = c_string(expr)?;
+					let expr_ptr = expr.as_ptr();
+					strings.push(expr);
+
+					Ok(sys::slang_SpecializationArg {
+						kind: sys::slang_SpecializationArg_Kind::Expr,
+						__bindgen_anon_1: sys::slang_SpecializationArg__bindgen_ty_1 {
+							expr: expr_ptr,
+						},
+					})
+				}
+			})
+			.collect::<Result<Vec<_>>>()?;
+
+		let mut specialized_component_type = null_mut();
+		let mut diagnostics = null_mut();
+
+		let result = vcall!(
+			self,
+			specialize(
+				args.as_ptr(),
+				args.len() as _,
+				&mut specialized_component_type,
+				&mut diagnostics
+			)
+		);
+		self.report_diagnostics(diagnostics);
+
+		let diagnostics = result_with_diagnostics(result, diagnostics)?;
+
+		Ok(WithDiagnostics {
+			value: ComponentType(unknown_from_raw(specialized_component_type)?, PhantomData),
+			diagnostics,
+		})
+	}
+
+	pub fn link(&self) -> Result<ComponentType<'a>> {
+		self.link_with_diagnostics().map(|result| result.value)
+	}
+
+	pub fn link_with_diagnostics(&self) -> Result<WithDiagnostics<ComponentType<'a>>> {
 		let mut linked_component_type = null_mut();
 		let mut diagnostics = null_mut();
 
-		result_from_blob(
-			vcall!(self, link(&mut linked_component_type, &mut diagnostics)),
+		let result = vcall!(self, link(&mut linked_component_type, &mut diagnostics));
+		self.report_diagnostics(diagnostics);
+
+		let diagnostics = result_with_diagnostics(result, diagnostics)?;
+
+		Ok(WithDiagnostics {
+			value: ComponentType(unknown_from_raw(linked_component_type)?, PhantomData),
 			diagnostics,
-		)?;
+		})
+	}
 
-		Ok(ComponentType(IUnknown(
-			std::ptr::NonNull::new(linked_component_type as *mut _).unwrap(),
-		)))
+	pub fn link_with_options(&self, options: &CompilerOptions) -> Result<ComponentType<'a>> {
+		self.link_with_options_with_diagnostics(options)
+			.map(|result| result.value)
+	}
+
+	pub fn link_with_options_with_diagnostics(
+		&self,
+		options: &CompilerOptions,
+	) -> Result<WithDiagnostics<ComponentType<'a>>> {
+		options.check()?;
+
+		let mut linked_component_type = null_mut();
+		let mut diagnostics = null_mut();
+
+		let result = vcall!(
+			self,
+			linkWithOptions(
+				&mut linked_component_type,
+				options.options.len() as _,
+				options.options.as_ptr() as _,
+				&mut diagnostics
+			)
+		);
+		self.report_diagnostics(diagnostics);
+
+		let diagnostics = result_with_diagnostics(result, diagnostics)?;
+
+		Ok(WithDiagnostics {
+			value: ComponentType(unknown_from_raw(linked_component_type)?, PhantomData),
+			diagnostics,
+		})
+	}
+
+	pub fn rename_entry_point(&self, new_name: &str) -> Result<ComponentType<'a>> {
+		let new_name = c_string(new_name)?;
+		let mut entry_point = null_mut();
+
+		let result = vcall!(self, renameEntryPoint(new_name.as_ptr(), &mut entry_point));
+
+		if succeeded(result) {
+			Ok(ComponentType(unknown_from_raw(entry_point)?, PhantomData))
+		} else {
+			Err(Error::Code(result.into()))
+		}
 	}
 
 	pub fn target_code(&self, target: i64) -> Result<Blob> {
+		self.target_code_with_diagnostics(target)
+			.map(|result| result.value)
+	}
+
+	pub fn target_code_with_diagnostics(&self, target: i64) -> Result<WithDiagnostics<Blob>> {
 		let mut code = null_mut();
 		let mut diagnostics = null_mut();
 
-		result_from_blob(
-			vcall!(self, getTargetCode(target, &mut code, &mut diagnostics)),
-			diagnostics,
-		)?;
+		let result = vcall!(self, getTargetCode(target, &mut code, &mut diagnostics));
+		self.report_diagnostics(diagnostics);
 
-		Ok(Blob(IUnknown(
-			std::ptr::NonNull::new(code as *mut _).unwrap(),
-		)))
+		let diagnostics = result_with_diagnostics(result, diagnostics)?;
+
+		Ok(WithDiagnostics {
+			value: Blob(unknown_from_raw(code)?),
+			diagnostics,
+		})
 	}
 
 	pub fn entry_point_code(&self, index: i64, target: i64) -> Result<Blob> {
+		self.entry_point_code_with_diagnostics(index, target)
+			.map(|result| result.value)
+	}
+
+	pub fn entry_point_code_with_diagnostics(
+		&self,
+		index: i64,
+		target: i64,
+	) -> Result<WithDiagnostics<Blob>> {
 		let mut code = null_mut();
 		let mut diagnostics = null_mut();
 
-		result_from_blob(
-			vcall!(
-				self,
-				getEntryPointCode(index, target, &mut code, &mut diagnostics)
-			),
+		let result = vcall!(
+			self,
+			getEntryPointCode(index, target, &mut code, &mut diagnostics)
+		);
+		self.report_diagnostics(diagnostics);
+
+		let diagnostics = result_with_diagnostics(result, diagnostics)?;
+
+		Ok(WithDiagnostics {
+			value: Blob(unknown_from_raw(code)?),
 			diagnostics,
-		)?;
+		})
+	}
+
+	pub fn entry_point_hash(&self, entry_point_index: i64, target_index: i64) -> Result<Blob> {
+		let mut hash = null_mut();
+		vcall!(
+			self,
+			getEntryPointHash(entry_point_index, target_index, &mut hash)
+		);
 
-		Ok(Blob(IUnknown(
-			std::ptr::NonNull::new(code as *mut _).unwrap(),
-		)))
+		Ok(Blob(unknown_from_raw(hash)?))
+	}
+
+	/// Returns the SHA-1 digest computed by [`ComponentType::entry_point_hash`].
+	///
+	/// Fails with [`Error::UnexpectedSize`] if Slang returns a digest that isn't 20 bytes long.
+	pub fn entry_point_hash_bytes(
+		&self,
+		entry_point_index: i64,
+		target_index: i64,
+	) -> Result<[u8; 20]> {
+		let hash = self.entry_point_hash(entry_point_index, target_index)?;
+		hash.as_slice()
+			.try_into()
+			.map_err(|_| Error::UnexpectedSize {
+				expected: 20,
+				actual: hash.as_slice().len(),
+			})
+	}
+
+	pub fn result_file_system(
+		&self,
+		entry_point_index: i64,
+		target_index: i64,
+	) -> Result<ResultFileSystem> {
+		let mut file_system = null_mut();
+		let result = vcall!(
+			self,
+			getResultAsFileSystem(entry_point_index, target_index, &mut file_system)
+		);
+
+		if succeeded(result) {
+			Ok(ResultFileSystem(unknown_from_raw(file_system)?))
+		} else {
+			Err(Error::Code(result.into()))
+		}
 	}
 
 	pub fn target_metadata(&self, target_index: i64) -> Result<Metadata> {
 		let mut metadata = null_mut();
 		let mut diagnostics = null_mut();
 
-		result_from_blob(
-			vcall!(
-				self,
-				getTargetMetadata(target_index, &mut metadata, &mut diagnostics)
-			),
-			diagnostics,
-		)?;
+		let result = vcall!(
+			self,
+			getTargetMetadata(target_index, &mut metadata, &mut diagnostics)
+		);
+		self.report_diagnostics(diagnostics);
 
-		Ok(Metadata(IUnknown(
-			std::ptr::NonNull::new(metadata as *mut _).unwrap(),
-		)))
+		result_from_blob(result, diagnostics)?;
+
+		Ok(Metadata(unknown_from_raw(metadata)?))
 	}
 
 	pub fn entry_point_metadata(
@@ -462,128 +1226,186 @@ impl ComponentType {
 		let mut metadata = null_mut();
 		let mut diagnostics = null_mut();
 
-		result_from_blob(
-			vcall!(
-				self,
-				getEntryPointMetadata(
-					entry_point_index,
-					target_index,
-					&mut metadata,
-					&mut diagnostics
-				)
-			),
-			diagnostics,
-		)?;
+		let result = vcall!(
+			self,
+			getEntryPointMetadata(
+				entry_point_index,
+				target_index,
+				&mut metadata,
+				&mut diagnostics
+			)
+		);
+		self.report_diagnostics(diagnostics);
 
-		Ok(Metadata(IUnknown(
-			std::ptr::NonNull::new(metadata as *mut _).unwrap(),
-		)))
+		result_from_blob(result, diagnostics)?;
+
+		Ok(Metadata(unknown_from_raw(metadata)?))
+	}
+
+	fn report_diagnostics(&self, diagnostics: *mut sys::slang_IBlob) {
+		let session = vcall!(self, getSession());
+		unsafe { diagnostics::report(session, diagnostics) };
 	}
 }
 
 #[repr(transparent)]
 #[derive(Clone)]
-pub struct EntryPoint(IUnknown);
+pub struct EntryPoint<'a>(IUnknown, PhantomData<&'a Session>);
 
-unsafe impl Interface for EntryPoint {
+unsafe impl Interface for EntryPoint<'_> {
 	type Vtable = sys::IEntryPointVtable;
 	const IID: UUID = uuid(0x8f241361_f5bd_4ca0_a3ac02f7fa2402b8);
 }
 
-impl From<EntryPoint> for ComponentType {
-	fn from(value: EntryPoint) -> Self {
+impl<'a> From<EntryPoint<'a>> for ComponentType<'a> {
+	fn from(value: EntryPoint<'a>) -> Self {
 		unsafe { std::mem::transmute(value) }
 	}
 }
 
-impl EntryPoint {
+impl<'a> EntryPoint<'a> {
 	pub fn function_reflection(&self) -> &reflection::Function {
 		let ptr = vcall!(self, getFunctionReflection());
 		unsafe { &*(ptr as *const _) }
 	}
 }
 
+fn entry_point_from_raw<'a>(
+	result: sys::SlangResult,
+	entry_point: *mut sys::slang_IEntryPoint,
+) -> Result<EntryPoint<'a>> {
+	let entry_point = unknown_from_raw(entry_point);
+
+	if succeeded(result) {
+		Ok(EntryPoint(entry_point?, PhantomData))
+	} else {
+		Err(Error::Code(result.into()))
+	}
+}
+
 #[repr(transparent)]
 #[derive(Clone)]
-pub struct TypeConformance(IUnknown);
+pub struct TypeConformance<'a>(IUnknown, PhantomData<&'a Session>);
 
-unsafe impl Interface for TypeConformance {
+unsafe impl Interface for TypeConformance<'_> {
 	type Vtable = sys::ITypeConformanceVtable;
 	const IID: UUID = uuid(0x73eb3147_e544_41b5_b8f0a244df21940b);
 }
 
-impl From<TypeConformance> for ComponentType {
-	fn from(value: TypeConformance) -> Self {
+impl<'a> From<TypeConformance<'a>> for ComponentType<'a> {
+	fn from(value: TypeConformance<'a>) -> Self {
 		unsafe { std::mem::transmute(value) }
 	}
 }
 
+/// Modules are owned by the [`Session`] that loaded them and use its non thread-safe state,
+/// so they can't be sent to other threads. Serialize them to move them between sessions.
+///
+/// ```compile_fail
+/// fn assert_send<T: Send>() {}
+/// assert_send::<shader_slang::Module>();
+/// ```
 #[repr(transparent)]
 #[derive(Clone)]
-pub struct Module(IUnknown);
+pub struct Module<'a>(IUnknown, PhantomData<&'a Session>);
 
-unsafe impl Interface for Module {
+unsafe impl Interface for Module<'_> {
 	type Vtable = sys::IModuleVtable;
 	const IID: UUID = uuid(0x0c720e64_8722_4d31_8990638a98b1c279);
 }
 
-impl From<Module> for ComponentType {
-	fn from(value: Module) -> Self {
+impl<'a> From<Module<'a>> for ComponentType<'a> {
+	fn from(value: Module<'a>) -> Self {
 		unsafe { std::mem::transmute(value) }
 	}
 }
 
-impl Module {
-	pub fn find_entry_point_by_name(&self, name: &str) -> Option<EntryPoint> {
-		let name = CString::new(name).unwrap();
+impl<'a> Module<'a> {
+	pub fn find_entry_point_by_name(&self, name: &str) -> Result<EntryPoint<'a>> {
+		let name = c_string(name)?;
 		let mut entry_point = null_mut();
-		vcall!(self, findEntryPointByName(name.as_ptr(), &mut entry_point));
-		Some(EntryPoint(IUnknown(std::ptr::NonNull::new(
-			entry_point as *mut _,
-		)?)))
+		let result = vcall!(self, findEntryPointByName(name.as_ptr(), &mut entry_point));
+		entry_point_from_raw(result, entry_point)
+	}
+
+	pub fn find_and_check_entry_point(&self, name: &str, stage: Stage) -> Result<EntryPoint<'a>> {
+		let name = c_string(name)?;
+		let mut entry_point = null_mut();
+		let mut diagnostics = null_mut();
+
+		let result = vcall!(
+			self,
+			findAndCheckEntryPoint(name.as_ptr(), stage, &mut entry_point, &mut diagnostics)
+		);
+		self.report_diagnostics(diagnostics);
+
+		result_from_blob(result, diagnostics)?;
+
+		Ok(EntryPoint(unknown_from_raw(entry_point)?, PhantomData))
 	}
 
 	pub fn entry_point_count(&self) -> u32 {
 		vcall!(self, getDefinedEntryPointCount()) as _
 	}
 
-	pub fn entry_point_by_index(&self, index: u32) -> Option<EntryPoint> {
+	pub fn entry_point_by_index(&self, index: u32) -> Result<EntryPoint<'a>> {
 		let mut entry_point = null_mut();
-		vcall!(self, getDefinedEntryPoint(index as _, &mut entry_point));
-		Some(EntryPoint(IUnknown(std::ptr::NonNull::new(
-			entry_point as *mut _,
-		)?)))
+		let result = vcall!(self, getDefinedEntryPoint(index as _, &mut entry_point));
+		entry_point_from_raw(result, entry_point)
+	}
+
+	pub fn entry_points(&self) -> impl ExactSizeIterator<Item = Result<EntryPoint<'a>>> {
+		(0..self.entry_point_count()).map(|i| self.entry_point_by_index(i))
 	}
 
-	pub fn entry_points(&self) -> impl ExactSizeIterator<Item = EntryPoint> {
-		(0..self.entry_point_count()).map(|i| self.entry_point_by_index(i).unwrap())
+	pub fn serialize(&self) -> Result<Blob> {
+		let mut blob = null_mut();
+		let result = vcall!(self, serialize(&mut blob));
+
+		if succeeded(result) {
+			Ok(Blob(unknown_from_raw(blob)?))
+		} else {
+			Err(Error::Code(result.into()))
+		}
+	}
+
+	/// Writes the serialized module, as a `.slang-module` file that can be loaded
+	/// with [`Session::load_module_from_ir_blob`].
+	///
+	/// Fails with [`ErrorCode::InvalidArg`] if the path contains a NUL byte,
+	/// or on Windows, if it isn't valid Unicode.
+	pub fn write_to_file(&self, path: impl AsRef<Path>) -> Result<()> {
+		let path = config::path_c_string(path.as_ref())?;
+		let result = vcall!(self, writeToFile(path.as_ptr()));
+
+		if succeeded(result) {
+			Ok(())
+		} else {
+			Err(Error::Code(result.into()))
+		}
 	}
 
-	pub fn name(&self) -> &str {
-		let name = vcall!(self, getName());
-		unsafe { CStr::from_ptr(name).to_str().unwrap() }
+	pub fn name(&self) -> Option<&str> {
+		str_from_ptr(vcall!(self, getName()))
 	}
 
-	pub fn file_path(&self) -> &str {
-		let path = vcall!(self, getFilePath());
-		unsafe { CStr::from_ptr(path).to_str().unwrap() }
+	pub fn file_path(&self) -> Option<&str> {
+		str_from_ptr(vcall!(self, getFilePath()))
 	}
 
-	pub fn unique_identity(&self) -> &str {
-		let identity = vcall!(self, getUniqueIdentity());
-		unsafe { CStr::from_ptr(identity).to_str().unwrap() }
+	pub fn unique_identity(&self) -> Option<&str> {
+		str_from_ptr(vcall!(self, getUniqueIdentity()))
 	}
 
 	pub fn dependency_file_count(&self) -> i32 {
 		vcall!(self, getDependencyFileCount()) as i32
 	}
 
-	pub fn dependency_file_path(&self, index: i32) -> &str {
-		let path = vcall!(self, getDependencyFilePath(index as i32));
-		unsafe { CStr::from_ptr(path).to_str().unwrap() }
+	pub fn dependency_file_path(&self, index: i32) -> Option<&str> {
+		str_from_ptr(vcall!(self, getDependencyFilePath(index as i32)))
 	}
 
-	pub fn dependency_file_paths(&self) -> impl ExactSizeIterator<Item = &str> {
+	pub fn dependency_file_paths(&self) -> impl ExactSizeIterator<Item = Option<&str>> {
 		(0..self.dependency_file_count()).map(|i| self.dependency_file_path(i))
 	}
 
@@ -591,6 +1413,11 @@ impl Module {
 		let ptr = vcall!(self, getModuleReflection());
 		unsafe { &*(ptr as *const _) }
 	}
+
+	fn report_diagnostics(&self, diagnostics: *mut sys::slang_IBlob) {
+		let session = unsafe { (self.vtable()._base.getSession)(self.as_raw()) };
+		unsafe { diagnostics::report(session, diagnostics) };
+	}
 }
 
 #[repr(transparent)]
@@ -630,6 +1457,26 @@ impl<'a> TargetDesc<'a> {
 		self
 	}
 
+	pub fn flags(mut self, flags: TargetFlags) -> Self {
+		self.inner.flags = flags.bits();
+		self
+	}
+
+	pub fn floating_point_mode(mut self, mode: FloatingPointMode) -> Self {
+		self.inner.floatingPointMode = mode;
+		self
+	}
+
+	pub fn line_directive_mode(mut self, mode: LineDirectiveMode) -> Self {
+		self.inner.lineDirectiveMode = mode;
+		self
+	}
+
+	pub fn force_glsl_scalar_buffer_layout(mut self, enable: bool) -> Self {
+		self.inner.forceGLSLScalarBufferLayout = enable;
+		self
+	}
+
 	pub fn options(mut self, options: &'a CompilerOptions) -> Self {
 		self.inner.compilerOptionEntries = options.options.as_ptr() as _;
 		self.inner.compilerOptionEntryCount = options.options.len() as _;
@@ -637,6 +1484,96 @@ impl<'a> TargetDesc<'a> {
 	}
 }
 
+/// The `SLANG_TARGET_FLAG_*` values, combined with `|`.
+///
+/// Slang's C++ `TargetDesc` defaults to [`TargetFlags::GENERATE_SPIRV_DIRECTLY`],
+/// while [`TargetDesc::default`] leaves all flags unset.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq, Hash)]
+pub struct TargetFlags(sys::SlangTargetFlags);
+
+impl TargetFlags {
+	pub const PARAMETER_BLOCKS_USE_REGISTER_SPACES: TargetFlags = TargetFlags(1 << 4);
+	pub const GENERATE_WHOLE_PROGRAM: TargetFlags = TargetFlags(1 << 8);
+	pub const DUMP_IR: TargetFlags = TargetFlags(1 << 9);
+	pub const GENERATE_SPIRV_DIRECTLY: TargetFlags = TargetFlags(1 << 10);
+
+	pub const fn empty() -> TargetFlags {
+		TargetFlags(0)
+	}
+
+	/// Keeps bits this crate has no constant for, so flags of newer Slang versions can be passed.
+	pub const fn from_bits_retain(bits: sys::SlangTargetFlags) -> TargetFlags {
+		TargetFlags(bits)
+	}
+
+	pub const fn bits(self) -> sys::SlangTargetFlags {
+		self.0
+	}
+
+	pub const fn is_empty(self) -> bool {
+		self.0 == 0
+	}
+
+	pub const fn contains(self, other: TargetFlags) -> bool {
+		self.0 & other.0 == other.0
+	}
+
+	pub fn insert(&mut self, other: TargetFlags) {
+		self.0 |= other.0;
+	}
+
+	pub fn remove(&mut self, other: TargetFlags) {
+		self.0 &= !other.0;
+	}
+}
+
+impl std::ops::BitOr for TargetFlags {
+	type Output = TargetFlags;
+
+	fn bitor(self, rhs: TargetFlags) -> TargetFlags {
+		TargetFlags(self.0 | rhs.0)
+	}
+}
+
+impl std::ops::BitOrAssign for TargetFlags {
+	fn bitor_assign(&mut self, rhs: TargetFlags) {
+		self.insert(rhs);
+	}
+}
+
+impl std::ops::BitAnd for TargetFlags {
+	type Output = TargetFlags;
+
+	fn bitand(self, rhs: TargetFlags) -> TargetFlags {
+		TargetFlags(self.0 & rhs.0)
+	}
+}
+
+/// The `slang_SessionDesc::flags` bits.
+///
+/// Slang currently defines no session flags, so this only carries the raw bits
+/// for Slang versions that add some.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq, Hash)]
+pub struct SessionFlags(sys::SlangSessionFlags);
+
+impl SessionFlags {
+	pub const fn empty() -> SessionFlags {
+		SessionFlags(0)
+	}
+
+	pub const fn from_bits_retain(bits: sys::SlangSessionFlags) -> SessionFlags {
+		SessionFlags(bits)
+	}
+
+	pub const fn bits(self) -> sys::SlangSessionFlags {
+		self.0
+	}
+
+	pub const fn is_empty(self) -> bool {
+		self.0 == 0
+	}
+}
+
 #[repr(transparent)]
 pub struct SessionDesc<'a> {
 	inner: sys::slang_SessionDesc,
@@ -681,16 +1618,186 @@ impl<'a> SessionDesc<'a> {
 		self.inner.compilerOptionEntryCount = options.options.len() as _;
 		self
 	}
+
+	pub fn preprocessor_macros(mut self, macros: &'a PreprocessorMacros) -> Self {
+		self.inner.preprocessorMacros = macros.macros.as_ptr();
+		self.inner.preprocessorMacroCount = macros.macros.len() as _;
+		self
+	}
+
+	pub fn flags(mut self, flags: SessionFlags) -> Self {
+		self.inner.flags = flags.bits();
+		self
+	}
+
+	pub fn default_matrix_layout_mode(mut self, mode: MatrixLayoutMode) -> Self {
+		self.inner.defaultMatrixLayoutMode = mode;
+		self
+	}
+
+	pub fn file_system(mut self, file_system: &'a VirtualFileSystem) -> Self {
+		self.inner.fileSystem = unsafe { file_system.as_raw() };
+		self
+	}
+
+	pub fn allow_glsl_syntax(mut self, enable: bool) -> Self {
+		self.inner.allowGLSLSyntax = enable;
+		self
+	}
+
+	pub fn enable_effect_annotations(mut self, enable: bool) -> Self {
+		self.inner.enableEffectAnnotations = enable;
+		self
+	}
+
+	pub fn skip_spirv_validation(mut self, skip: bool) -> Self {
+		self.inner.skipSPIRVValidation = skip;
+		self
+	}
+
+	/// Fails if the options of the session or of one of its targets couldn't be converted.
+	fn check_options(&self) -> Result<()> {
+		let targets = raw_slice(self.inner.targets, self.inner.targetCount as _);
+
+		check_option_entries(raw_slice(
+			self.inner.compilerOptionEntries,
+			self.inner.compilerOptionEntryCount as _,
+		))?;
+
+		targets.iter().try_for_each(|target| {
+			check_option_entries(raw_slice(
+				target.compilerOptionEntries,
+				target.compilerOptionEntryCount as _,
+			))
+		})
+	}
 }
 
-macro_rules! option {
-	($name:ident, $func:ident($p_name:ident: $p_type:ident)) => {
-		#[inline(always)]
-		pub fn $func(self, $p_name: $p_type) -> Self {
-			self.push_ints(CompilerOptionName::$name, $p_name as _, 0)
-		}
+/// Borrows the arrays of a description, which may be null when empty.
+fn raw_slice<'a, T>(ptr: *const T, len: usize) -> &'a [T] {
+	if len == 0 {
+		&[]
+	} else {
+		unsafe { std::slice::from_raw_parts(ptr, len) }
+	}
+}
+
+/// A session description parsed by [`GlobalSession::parse_command_line`].
+///
+/// The targets, search paths, macros and options point into an allocation owned by Slang,
+/// which is kept alive together with the description.
+pub struct OwnedSessionDesc {
+	desc: SessionDesc<'static>,
+	_aux_allocation: Option<IUnknown>,
+}
+
+impl OwnedSessionDesc {
+	pub fn desc(&self) -> &SessionDesc<'_> {
+		&self.desc
+	}
+}
+
+/// Preprocessor macros defined for every module loaded into a session.
+#[derive(Default)]
+pub struct PreprocessorMacros {
+	strings: Vec<(CString, CString)>,
+	macros: Vec<sys::slang_PreprocessorMacroDesc>,
+}
+
+// The descriptions only point into `strings`, which is owned and never mutated after being pushed.
+unsafe impl Send for PreprocessorMacros {}
+unsafe impl Sync for PreprocessorMacros {}
+
+impl Clone for PreprocessorMacros {
+	fn clone(&self) -> Self {
+		self.strings
+			.iter()
+			.fold(Self::default(), |macros, (name, value)| {
+				macros.push(name.clone(), value.clone())
+			})
+	}
+}
+
+impl PreprocessorMacros {
+	/// Defines the macro `name` with the given `value`.
+	///
+	/// Fails with [`ErrorCode::InvalidArg`] if `name` or `value` contains a NUL byte.
+	pub fn define(self, name: &str, value: &str) -> Result<Self> {
+		Ok(self.push(c_string(name)?, c_string(value)?))
+	}
+
+	fn push(mut self, name: CString, value: CString) -> Self {
+		// Moving a `CString` doesn't move its heap allocation, so the pointers stay valid.
+		self.macros.push(sys::slang_PreprocessorMacroDesc {
+			name: name.as_ptr(),
+			value: value.as_ptr(),
+		});
+		self.strings.push((name, value));
+		self
+	}
+}
+
+/// Converts a typed option parameter to the integer Slang stores in the option entry.
+trait IntOptionValue {
+	fn int_value(self) -> i32;
+}
+
+macro_rules! int_option_value {
+	($($type:ty),*) => {
+		$(impl IntOptionValue for $type {
+			fn int_value(self) -> i32 {
+				self as _
+			}
+		})*
 	};
+}
+
+int_option_value!(
+	bool,
+	ArchiveType,
+	CompileTarget,
+	DebugInfoFormat,
+	DebugInfoLevel,
+	EmitSpirvMethod,
+	FloatingPointMode,
+	FpDenormalMode,
+	LineDirectiveMode,
+	OptimizationLevel,
+	PassThrough,
+	SourceLanguage,
+	Stage,
+	VulkanShiftKind
+);
+
+impl IntOptionValue for i32 {
+	fn int_value(self) -> i32 {
+		self
+	}
+}
 
+impl IntOptionValue for ProfileID {
+	fn int_value(self) -> i32 {
+		self.0 as _
+	}
+}
+
+impl IntOptionValue for CapabilityID {
+	fn int_value(self) -> i32 {
+		self.0 as _
+	}
+}
+
+/// The register kinds shifted by [`CompilerOptions::vulkan_bind_shift`],
+/// matching the `-fvk-{b,t,u,s}-shift` options of `slangc`.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+pub enum VulkanShiftKind {
+	ConstantBuffer,
+	ShaderResource,
+	UnorderedAccess,
+	Sampler,
+}
+
+macro_rules! option {
 	($name:ident, $func:ident($p_name:ident: &str)) => {
 		#[inline(always)]
 		pub fn $func(self, $p_name: &str) -> Self {
@@ -704,14 +1811,87 @@ macro_rules! option {
 			self.push_str2(CompilerOptionName::$name, $p_name1, $p_name2)
 		}
 	};
+
+	($name:ident, $func:ident($p_name:ident: $p_type:ident)) => {
+		#[inline(always)]
+		pub fn $func(self, $p_name: $p_type) -> Self {
+			self.push_ints(CompilerOptionName::$name, $p_name.int_value(), 0)
+		}
+	};
+
+	($name:ident, $func:ident($p_name1:ident: $p_type1:ident, $p_name2:ident: $p_type2:ident)) => {
+		#[inline(always)]
+		pub fn $func(self, $p_name1: $p_type1, $p_name2: $p_type2) -> Self {
+			self.push_ints(
+				CompilerOptionName::$name,
+				$p_name1.int_value(),
+				$p_name2.int_value(),
+			)
+		}
+	};
+
+	// The block packs the parameters into the two integers of the entry.
+	($name:ident, $func:ident($($p_name:ident: $p_type:ty),*) $ints:block) => {
+		#[inline(always)]
+		pub fn $func(self, $($p_name: $p_type),*) -> Self {
+			let (i0, i1) = $ints;
+			self.push_ints(CompilerOptionName::$name, i0, i1)
+		}
+	};
 }
 
+/// Declares a builder method for each option, and the list of names that have one.
+macro_rules! options {
+	($($name:ident => $func:ident($($params:tt)*) $($ints:block)?;)*) => {
+		impl CompilerOptions {
+			$(option!($name, $func($($params)*) $($ints)?);)*
+
+			#[cfg(test)]
+			pub(crate) const NAMES: &'static [CompilerOptionName] = &[$(CompilerOptionName::$name),*];
+		}
+	};
+}
+
+/// A string argument with a NUL byte can't be passed to Slang. It is recorded instead,
+/// and [`GlobalSession::create_session`], [`GlobalSession::session_desc_digest`] and
+/// [`ComponentType::link_with_options`] fail with [`ErrorCode::InvalidArg`] when given these options.
 #[derive(Default)]
 pub struct CompilerOptions {
 	strings: Vec<CString>,
 	options: Vec<sys::slang_CompilerOptionEntry>,
 }
 
+// The entries only point into `strings`, which is owned and never mutated after being pushed.
+unsafe impl Send for CompilerOptions {}
+unsafe impl Sync for CompilerOptions {}
+
+impl Clone for CompilerOptions {
+	fn clone(&self) -> Self {
+		let strings = self.strings.clone();
+
+		// Point the cloned entries at the cloned strings instead of the ones owned by `self`.
+		let remap = |ptr: *const i8| {
+			self.strings
+				.iter()
+				.position(|s| s.as_ptr() == ptr)
+				.map_or(ptr, |i| strings[i].as_ptr())
+		};
+
+		let options = self
+			.options
+			.iter()
+			.map(|entry| {
+				let mut entry = *entry;
+				entry.value.stringValue0 = remap(entry.value.stringValue0);
+				entry.value.stringValue1 = remap(entry.value.stringValue1);
+				entry
+			})
+			.collect();
+
+		Self { strings, options }
+	}
+}
+
 impl CompilerOptions {
 	fn push_ints(mut self, name: CompilerOptionName, i0: i32, i1: i32) -> Self {
 		self.options.push(sys::slang_CompilerOptionEntry {
@@ -744,7 +1924,10 @@ impl CompilerOptions {
 	}
 
 	fn push_str1(mut self, name: CompilerOptionName, s0: &str) -> Self {
-		let s0 = CString::new(s0).unwrap();
+		let Ok(s0) = CString::new(s0) else {
+			return self.push_invalid();
+		};
+
 		let s0_ptr = s0.as_ptr();
 		self.strings.push(s0);
 
@@ -752,62 +1935,143 @@ impl CompilerOptions {
 	}
 
 	fn push_str2(mut self, name: CompilerOptionName, s0: &str, s1: &str) -> Self {
-		let s0 = CString::new(s0).unwrap();
-		let s0_ptr = s0.as_ptr();
-		self.strings.push(s0);
+		let (Ok(s0), Ok(s1)) = (CString::new(s0), CString::new(s1)) else {
+			return self.push_invalid();
+		};
 
-		let s1 = CString::new(s1).unwrap();
-		let s1_ptr = s1.as_ptr();
+		let (s0_ptr, s1_ptr) = (s0.as_ptr(), s1.as_ptr());
+		self.strings.push(s0);
 		self.strings.push(s1);
 
 		self.push_strings(name, s0_ptr, s1_ptr)
 	}
+
+	/// Stands in for an option whose string argument contained a NUL byte.
+	fn push_invalid(self) -> Self {
+		self.push_ints(INVALID_OPTION, 0, 0)
+	}
+
+	fn check(&self) -> Result<()> {
+		check_option_entries(&self.options)
+	}
 }
 
-impl CompilerOptions {
-	option!(MacroDefine, macro_define(key: &str, value: &str));
-	option!(Include, include(path: &str));
-	option!(Language, language(language: SourceLanguage));
-	option!(MatrixLayoutColumn, matrix_layout_column(enable: bool));
-	option!(MatrixLayoutRow, matrix_layout_row(enable: bool));
+// No real option has this name, so it can mark the options that couldn't be converted.
+const INVALID_OPTION: CompilerOptionName = CompilerOptionName::CountOf;
 
-	#[inline(always)]
-	pub fn profile(self, profile: ProfileID) -> Self {
-		self.push_ints(CompilerOptionName::Profile, profile.0 as _, 0)
+fn check_option_entries(entries: &[sys::slang_CompilerOptionEntry]) -> Result<()> {
+	if entries.iter().any(|entry| entry.name == INVALID_OPTION) {
+		Err(Error::Code(ErrorCode::InvalidArg))
+	} else {
+		Ok(())
 	}
+}
 
-	option!(Stage, stage(stage: Stage));
-	option!(Target, target(target: CompileTarget));
-	option!(WarningsAsErrors, warnings_as_errors(warning_codes: &str));
-	option!(DisableWarnings, disable_warnings(warning_codes: &str));
-	option!(EnableWarning, enable_warning(warning_code: &str));
-	option!(DisableWarning, disable_warning(warning_code: &str));
-	option!(ReportDownstreamTime, report_downstream_time(enable: bool));
-	option!(ReportPerfBenchmark, report_perf_benchmark(enable: bool));
-	option!(SkipSPIRVValidation, skip_spirv_validation(enable: bool));
+options! {
+	MacroDefine => macro_define(key: &str, value: &str);
+	EntryPointName => entry_point_name(name: &str);
+	Specialize => specialize(type_name: &str);
+	Include => include(path: &str);
+	Language => language(language: SourceLanguage);
+	MatrixLayoutColumn => matrix_layout_column(enable: bool);
+	MatrixLayoutRow => matrix_layout_row(enable: bool);
+	ZeroInitialize => zero_initialize(enable: bool);
+	IgnoreCapabilities => ignore_capabilities(enable: bool);
+	RestrictiveCapabilityCheck => restrictive_capability_check(enable: bool);
+	ModuleName => module_name(name: &str);
+	Profile => profile(profile: ProfileID);
+	Stage => stage(stage: Stage);
+	Target => target(target: CompileTarget);
+	WarningsAsErrors => warnings_as_errors(warning_codes: &str);
+	DisableWarnings => disable_warnings(warning_codes: &str);
+	EnableWarning => enable_warning(warning_code: &str);
+	DisableWarning => disable_warning(warning_code: &str);
+	DumpWarningDiagnostics => dump_warning_diagnostics(enable: bool);
+	EmitIr => emit_ir(enable: bool);
+	ReportDownstreamTime => report_downstream_time(enable: bool);
+	ReportPerfBenchmark => report_perf_benchmark(enable: bool);
+	ReportCheckpointIntermediates => report_checkpoint_intermediates(enable: bool);
+	SkipSPIRVValidation => skip_spirv_validation(enable: bool);
+	DisableShortCircuit => disable_short_circuit(enable: bool);
+	MinimumSlangOptimization => minimum_slang_optimization(enable: bool);
+	DisableNonEssentialValidations => disable_non_essential_validations(enable: bool);
+	DisableSourceMap => disable_source_map(enable: bool);
+	UnscopedEnum => unscoped_enum(enable: bool);
+	PreserveParameters => preserve_parameters(enable: bool);
 
 	// Target
-	#[inline(always)]
-	pub fn capability(self, capability: CapabilityID) -> Self {
-		self.push_ints(CompilerOptionName::Capability, capability.0 as _, 0)
-	}
-
-	option!(DefaultImageFormatUnknown, default_image_format_unknown(enable: bool));
-	option!(DisableDynamicDispatch, disable_dynamic_dispatch(enable: bool));
-	option!(DisableSpecialization, disable_specialization(enable: bool));
-	option!(FloatingPointMode, floating_point_mode(mode: FloatingPointMode));
-	option!(DebugInformation, debug_information(level: DebugInfoLevel));
-	option!(LineDirectiveMode, line_directive_mode(mode: LineDirectiveMode));
-	option!(Optimization, optimization(level: OptimizationLevel));
-	option!(Obfuscate, obfuscate(enable: bool));
-	option!(VulkanUseEntryPointName, vulkan_use_entry_point_name(enable: bool));
-	option!(GLSLForceScalarLayout, glsl_force_scalar_layout(enable: bool));
-	option!(EmitSpirvDirectly, emit_spirv_directly(enable: bool));
+	Capability => capability(capability: CapabilityID);
+	DefaultImageFormatUnknown => default_image_format_unknown(enable: bool);
+	DisableDynamicDispatch => disable_dynamic_dispatch(enable: bool);
+	DisableSpecialization => disable_specialization(enable: bool);
+	FloatingPointMode => floating_point_mode(mode: FloatingPointMode);
+	DebugInformation => debug_information(level: DebugInfoLevel);
+	LineDirectiveMode => line_directive_mode(mode: LineDirectiveMode);
+	Optimization => optimization(level: OptimizationLevel);
+	Obfuscate => obfuscate(enable: bool);
+	VulkanBindShift => vulkan_bind_shift(kind: VulkanShiftKind, set: i32, shift: i32) {
+		(((kind as i32) << 24) | set, shift)
+	};
+	VulkanBindGlobals => vulkan_bind_globals(index: i32, set: i32);
+	VulkanInvertY => vulkan_invert_y(enable: bool);
+	VulkanUseDxPositionW => vulkan_use_dx_position_w(enable: bool);
+	VulkanUseEntryPointName => vulkan_use_entry_point_name(enable: bool);
+	VulkanUseGLLayout => vulkan_use_gl_layout(enable: bool);
+	VulkanEmitReflection => vulkan_emit_reflection(enable: bool);
+	GLSLForceScalarLayout => glsl_force_scalar_layout(enable: bool);
+	EnableEffectAnnotations => enable_effect_annotations(enable: bool);
+	EmitSpirvViaGLSL => emit_spirv_via_glsl(enable: bool);
+	EmitSpirvDirectly => emit_spirv_directly(enable: bool);
+	SPIRVCoreGrammarJSON => spirv_core_grammar_json(path: &str);
+	IncompleteLibrary => incomplete_library(enable: bool);
+
+	// Downstream
+	DownstreamArgs => downstream_args(compiler: &str, args: &str);
+	PassThrough => pass_through(pass_through: PassThrough);
 
 	// Debugging
-	option!(NoCodeGen, no_code_gen(enable: bool));
+	DumpAst => dump_ast(enable: bool);
+	DumpIntermediatePrefix => dump_intermediate_prefix(prefix: &str);
+	DumpIntermediates => dump_intermediates(enable: bool);
+	DumpIr => dump_ir(enable: bool);
+	DumpIrIds => dump_ir_ids(enable: bool);
+	PreprocessorOutput => preprocessor_output(enable: bool);
+	OutputIncludes => output_includes(enable: bool);
+	SerialIr => serial_ir(enable: bool);
+	SkipCodeGen => skip_code_gen(enable: bool);
+	ValidateIr => validate_ir(enable: bool);
+	VerbosePaths => verbose_paths(enable: bool);
+	VerifyDebugSerialIr => verify_debug_serial_ir(enable: bool);
+	NoCodeGen => no_code_gen(enable: bool);
 
 	// Experimental
-	option!(NoMangle, no_mangle(enable: bool));
-	option!(ValidateUniformity, validate_uniformity(enable: bool));
+	NoMangle => no_mangle(enable: bool);
+	NoHLSLBinding => no_hlsl_binding(enable: bool);
+	NoHLSLPackConstantBufferElements => no_hlsl_pack_constant_buffer_elements(enable: bool);
+	ValidateUniformity => validate_uniformity(enable: bool);
+	AllowGLSL => allow_glsl(enable: bool);
+	EnableExperimentalPasses => enable_experimental_passes(enable: bool);
+	BindlessSpaceIndex => bindless_space_index(index: i32);
+
+	// Internal
+	ArchiveType => archive_type(archive_type: ArchiveType);
+	ReferenceModule => reference_module(path: &str);
+	TrackLiveness => track_liveness(enable: bool);
+	LoopInversion => loop_inversion(enable: bool);
+	ParameterBlocksUseRegisterSpaces => parameter_blocks_use_register_spaces(enable: bool);
+
+	// Parsed options only
+	DebugInformationFormat => debug_information_format(format: DebugInfoFormat);
+	VulkanBindShiftAll => vulkan_bind_shift_all(kind: VulkanShiftKind, shift: i32);
+	GenerateWholeProgram => generate_whole_program(enable: bool);
+	UseUpToDateBinaryModule => use_up_to_date_binary_module(enable: bool);
+	EmbedDownstreamIR => embed_downstream_ir(enable: bool);
+	ForceDXLayout => force_dx_layout(enable: bool);
+	EmitSpirvMethod => emit_spirv_method(method: EmitSpirvMethod);
+	EmitReflectionJSON => emit_reflection_json(enable: bool);
+	SkipDownstreamLinking => skip_downstream_linking(enable: bool);
+	EmitSeparateDebug => emit_separate_debug(enable: bool);
+	DenormalModeFp16 => denormal_mode_fp16(mode: FpDenormalMode);
+	DenormalModeFp32 => denormal_mode_fp32(mode: FpDenormalMode);
+	DenormalModeFp64 => denormal_mode_fp64(mode: FpDenormalMode);
 }
@@ -6,8 +6,7 @@ pub struct Decl(sys::SlangReflectionDecl);
 
 impl Decl {
 	pub fn name(&self) -> Option<&str> {
-		let name = rcall!(spReflectionDecl_getName(self));
-		(!name.is_null()).then(|| unsafe { std::ffi::CStr::from_ptr(name).to_str().unwrap() })
+		rcall!(spReflectionDecl_getName(self) as Option<&str>)
 	}
 
 	pub fn kind(&self) -> DeclKind {
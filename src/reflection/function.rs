@@ -42,7 +42,7 @@ impl Function {
 		global_session: &GlobalSession,
 		name: &str,
 	) -> Option<&UserAttribute> {
-		let name = std::ffi::CString::new(name).unwrap();
+		let name = std::ffi::CString::new(name).ok()?;
 		rcall!(spReflectionFunction_FindUserAttributeByName(
 			self,
 			global_session.as_raw(),
@@ -32,7 +32,7 @@ impl Shader {
 	}
 
 	pub fn find_type_parameter_by_name(&self, name: &str) -> Option<&TypeParameter> {
-		let name = std::ffi::CString::new(name).unwrap();
+		let name = std::ffi::CString::new(name).ok()?;
 		rcall!(spReflection_FindTypeParameter(self, name.as_ptr()) as Option<&TypeParameter>)
 	}
 
@@ -49,7 +49,7 @@ impl Shader {
 	}
 
 	pub fn find_entry_point_by_name(&self, name: &str) -> Option<&EntryPoint> {
-		let name = std::ffi::CString::new(name).unwrap();
+		let name = std::ffi::CString::new(name).ok()?;
 		rcall!(spReflection_findEntryPointByName(self, name.as_ptr()) as Option<&EntryPoint>)
 	}
 
@@ -62,17 +62,17 @@ impl Shader {
 	}
 
 	pub fn find_type_by_name(&self, name: &str) -> Option<&Type> {
-		let name = std::ffi::CString::new(name).unwrap();
+		let name = std::ffi::CString::new(name).ok()?;
 		rcall!(spReflection_FindTypeByName(self, name.as_ptr()) as Option<&Type>)
 	}
 
 	pub fn find_function_by_name(&self, name: &str) -> Option<&Function> {
-		let name = std::ffi::CString::new(name).unwrap();
+		let name = std::ffi::CString::new(name).ok()?;
 		rcall!(spReflection_FindFunctionByName(self, name.as_ptr()) as Option<&Function>)
 	}
 
 	pub fn find_function_by_name_in_type(&self, ty: &Type, name: &str) -> Option<&Function> {
-		let name = std::ffi::CString::new(name).unwrap();
+		let name = std::ffi::CString::new(name).ok()?;
 		rcall!(
 			spReflection_FindFunctionByNameInType(self, ty as *const _ as *mut _, name.as_ptr())
 				as Option<&Function>
@@ -80,7 +80,7 @@ impl Shader {
 	}
 
 	pub fn find_var_by_name_in_type(&self, ty: &Type, name: &str) -> Option<&Variable> {
-		let name = std::ffi::CString::new(name).unwrap();
+		let name = std::ffi::CString::new(name).ok()?;
 		rcall!(
 			spReflection_FindVarByNameInType(self, ty as *const _ as *mut _, name.as_ptr())
 				as Option<&Variable>
@@ -138,12 +138,12 @@ impl Shader {
 
 		(!result.is_null()).then(|| {
 			let slice = unsafe { std::slice::from_raw_parts(result as *const u8, len as usize) };
-			std::str::from_utf8(slice).unwrap()
-		})
+			std::str::from_utf8(slice).ok()
+		})?
 	}
 
-	pub fn hashed_strings(&self) -> impl ExactSizeIterator<Item = &str> {
-		(0..self.hashed_string_count() as usize).map(|i| self.hashed_string(i as u64).unwrap())
+	pub fn hashed_strings(&self) -> impl ExactSizeIterator<Item = Option<&str>> {
+		(0..self.hashed_string_count() as usize).map(|i| self.hashed_string(i as u64))
 	}
 
 	pub fn global_params_type_layout(&self) -> Option<&TypeLayout> {
@@ -1,7 +1,7 @@
 use super::{Generic, UserAttribute, Variable, rcall};
 use crate::{
-	Blob, Error, IUnknown, ResourceAccess, ResourceShape, Result, ScalarType, TypeKind, succeeded,
-	sys,
+	Blob, Error, ResourceAccess, ResourceShape, Result, ScalarType, TypeKind, succeeded, sys,
+	unknown_from_raw,
 };
 
 #[repr(transparent)]
@@ -95,12 +95,10 @@ impl Type {
 		let mut name = std::ptr::null_mut();
 		let result = rcall!(spReflectionType_GetFullName(self, &mut name));
 
-		if succeeded(result) && !name.is_null() {
-			Ok(Blob(IUnknown(
-				std::ptr::NonNull::new(name as *mut _).unwrap(),
-			)))
+		if succeeded(result) {
+			Ok(Blob(unknown_from_raw(name)?))
 		} else {
-			Err(Error::Code(result))
+			Err(Error::Code(result.into()))
 		}
 	}
 
@@ -117,7 +115,7 @@ impl Type {
 	}
 
 	pub fn find_user_attribute_by_name(&self, name: &str) -> Option<&UserAttribute> {
-		let name = std::ffi::CString::new(name).unwrap();
+		let name = std::ffi::CString::new(name).ok()?;
 		rcall!(
 			spReflectionType_FindUserAttributeByName(self, name.as_ptr()) as Option<&UserAttribute>
 		)
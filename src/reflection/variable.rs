@@ -34,7 +34,7 @@ impl Variable {
 		global_session: &GlobalSession,
 		name: &str,
 	) -> Option<&UserAttribute> {
-		let name = std::ffi::CString::new(name).unwrap();
+		let name = std::ffi::CString::new(name).ok()?;
 		rcall!(spReflectionVariable_FindUserAttributeByName(
 			self,
 			global_session.as_raw(),
@@ -0,0 +1,145 @@
+use std::panic::{AssertUnwindSafe, catch_unwind, resume_unwind};
+use std::sync::mpsc::{Receiver, Sender, channel};
+use std::sync::{Arc, Mutex};
+use std::thread::JoinHandle;
+
+use crate::{GlobalSession, Session, SessionConfig};
+
+type Job = Box<dyn FnOnce(&Session) + Send>;
+
+/// Runs compile jobs on a fixed number of worker threads.
+///
+/// Slang sessions are not thread-safe and their objects use non-atomic reference counts,
+/// so [`GlobalSession`], [`Session`] and everything created from them stay on one thread.
+/// Instead, every worker creates its own global session and session, and only the inputs
+/// and outputs of a job cross threads.
+pub struct SessionPool {
+	sender: Option<Sender<Job>>,
+	workers: Vec<JoinHandle<()>>,
+}
+
+/// The pending result of a job submitted to a [`SessionPool`].
+pub struct JobHandle<T> {
+	receiver: Receiver<std::thread::Result<T>>,
+}
+
+impl<T> JobHandle<T> {
+	/// Blocks until the job has finished, resuming the panic if the job panicked.
+	///
+	/// Returns `None` if the job was dropped without running, because no worker was left to run it.
+	pub fn wait(self) -> Option<T> {
+		match self.receiver.recv() {
+			Ok(Ok(value)) => Some(value),
+			Ok(Err(panic)) => resume_unwind(panic),
+			Err(_) => None,
+		}
+	}
+}
+
+impl SessionPool {
+	/// Starts `workers` threads, each calling `create_session` once with its own global session.
+	///
+	/// Returns `None` if `workers` is zero, or if any worker fails to create its global session
+	/// or session.
+	pub fn new(
+		workers: usize,
+		create_session: impl Fn(&GlobalSession) -> Option<Session> + Send + Sync + 'static,
+	) -> Option<SessionPool> {
+		if workers == 0 {
+			return None;
+		}
+
+		let create_session = Arc::new(create_session);
+		let (sender, receiver) = channel::<Job>();
+		let receiver = Arc::new(Mutex::new(receiver));
+		let (ready_sender, ready_receiver) = channel();
+
+		let workers = (0..workers)
+			.map(|_| {
+				let create_session = create_session.clone();
+				let receiver = receiver.clone();
+				let ready_sender = ready_sender.clone();
+
+				std::thread::spawn(move || {
+					let session = GlobalSession::new()
+						.ok()
+						.and_then(|global_session| create_session(&global_session));
+
+					let Some(session) = session else {
+						let _ = ready_sender.send(false);
+						return;
+					};
+
+					let _ = ready_sender.send(true);
+					drop(ready_sender);
+
+					loop {
+						// The lock is released before running the job, so workers run jobs in parallel.
+						let job = receiver.lock().unwrap().recv();
+
+						match job {
+							Ok(job) => job(&session),
+							Err(_) => break,
+						}
+					}
+				})
+			})
+			.collect();
+
+		drop(ready_sender);
+
+		let pool = SessionPool {
+			sender: Some(sender),
+			workers,
+		};
+
+		// A worker that panicked while starting drops its sender without reporting.
+		let ready = ready_receiver.iter().filter(|&ready| ready).count();
+		(ready == pool.workers.len()).then_some(pool)
+	}
+
+	/// Starts `workers` threads that each create their session from `config`.
+	///
+	/// Profiles and capabilities found through any global session can be used in `config`,
+	/// as their IDs are the same for every global session.
+	pub fn with_config(workers: usize, config: SessionConfig) -> Option<SessionPool> {
+		Self::new(workers, move |global_session| {
+			global_session.create_session(&config).ok()
+		})
+	}
+
+	pub fn worker_count(&self) -> usize {
+		self.workers.len()
+	}
+
+	/// Queues `job` to run on the session of the next idle worker.
+	///
+	/// Modules loaded by a job stay loaded in that worker's session for later jobs.
+	pub fn execute<T: Send + 'static>(
+		&self,
+		job: impl FnOnce(&Session) -> T + Send + 'static,
+	) -> JobHandle<T> {
+		let (sender, receiver) = channel();
+
+		let job: Job = Box::new(move |session| {
+			let result = catch_unwind(AssertUnwindSafe(|| job(session)));
+			let _ = sender.send(result);
+		});
+
+		// If all workers are gone, the job is dropped and `JobHandle::wait` reports it.
+		let _ = self.sender.as_ref().unwrap().send(job);
+
+		JobHandle { receiver }
+	}
+}
+
+impl Drop for SessionPool {
+	fn drop(&mut self) {
+		// Workers finish the queued jobs and exit once the queue is closed.
+		drop(self.sender.take());
+
+		for worker in self.workers.drain(..) {
+			let _ = worker.join();
+		}
+	}
+}
@@ -1,5 +1,34 @@
 use crate as slang;
 
+/// A session config with a single SPIR-V target, which most tests compile to.
+fn spirv_config(global_session: &slang::GlobalSession) -> slang::SessionConfig {
+	slang::SessionConfig::default().target(
+		slang::TargetConfig::default()
+			.format(slang::CompileTarget::Spirv)
+			.profile(global_session.find_profile("glsl_450").unwrap()),
+	)
+}
+
+fn spirv_session(global_session: &slang::GlobalSession) -> slang::Session {
+	global_session
+		.create_session(&spirv_config(global_session))
+		.unwrap()
+}
+
+/// Composes `module` with its `main` entry point and links the result.
+fn link_main<'a>(
+	session: &'a slang::Session,
+	module: slang::Module<'a>,
+) -> slang::ComponentType<'a> {
+	let entry_point = module.find_entry_point_by_name("main").unwrap();
+
+	session
+		.create_composite_component_type(&[module.into(), entry_point.into()])
+		.unwrap()
+		.link()
+		.unwrap()
+}
+
 #[test]
 fn compile() {
 	let global_session = slang::GlobalSession::new().unwrap();
@@ -13,7 +42,7 @@ fn compile() {
 
 	let target_desc = slang::TargetDesc::default()
 		.format(slang::CompileTarget::Spirv)
-		.profile(global_session.find_profile("glsl_450"));
+		.profile(global_session.find_profile("glsl_450").unwrap());
 
 	let targets = [target_desc];
 	let search_paths = [search_path.as_ptr()];
@@ -41,3 +70,1663 @@ fn compile() {
 	let shader_bytecode = linked_program.entry_point_code(0, 0).unwrap();
 	assert_ne!(shader_bytecode.as_slice().len(), 0);
 }
+
+#[test]
+fn entry_point_hash() {
+	let global_session = slang::GlobalSession::new().unwrap();
+
+	let config = spirv_config(&global_session).target(
+		slang::TargetConfig::default()
+			.format(slang::CompileTarget::Hlsl)
+			.profile(global_session.find_profile("sm_5_0").unwrap()),
+	);
+
+	let session = global_session.create_session(&config).unwrap();
+	let module = session
+		.load_module_from_source_string(
+			"hash",
+			"hash.slang",
+			"RWStructuredBuffer<float> output;
+			[shader(\"compute\")] [numthreads(1, 1, 1)] void first() { output[0] = 1.0; }
+			[shader(\"compute\")] [numthreads(1, 1, 1)] void second() { output[0] = 2.0; }",
+		)
+		.unwrap();
+
+	let first = module.find_entry_point_by_name("first").unwrap();
+	let second = module.find_entry_point_by_name("second").unwrap();
+
+	let linked_program = session
+		.create_composite_component_type(&[module.into(), first.into(), second.into()])
+		.unwrap()
+		.link()
+		.unwrap();
+
+	let hash = linked_program.entry_point_hash_bytes(0, 0).unwrap();
+	assert_eq!(
+		linked_program.entry_point_hash(0, 0).unwrap().as_slice(),
+		hash
+	);
+
+	// The same entry point and target always hash the same.
+	assert_eq!(linked_program.entry_point_hash_bytes(0, 0).unwrap(), hash);
+
+	assert_ne!(linked_program.entry_point_hash_bytes(1, 0).unwrap(), hash);
+	assert_ne!(linked_program.entry_point_hash_bytes(0, 1).unwrap(), hash);
+}
+
+#[test]
+fn serialize_module() {
+	let global_session = slang::GlobalSession::new().unwrap();
+
+	let session = spirv_session(&global_session);
+	let module = session
+		.load_module_from_source_string(
+			"serialized",
+			"serialized.slang",
+			"[shader(\"compute\")] [numthreads(1, 1, 1)] void main() {}",
+		)
+		.unwrap();
+
+	let ir_blob = module.serialize().unwrap();
+	assert_ne!(ir_blob.as_slice().len(), 0);
+
+	// Round trip through Rust owned bytes, as if the module was read from a cache.
+	let ir_blob = slang::Blob::from_vec(ir_blob.as_slice().to_vec());
+
+	let session = spirv_session(&global_session);
+	let module = session
+		.load_module_from_ir_blob("serialized", "serialized.slang-module", &ir_blob)
+		.unwrap();
+	assert_eq!(module.entry_point_count(), 1);
+
+	let path = std::env::temp_dir().join(format!("slang-rs-{}.slang-module", std::process::id()));
+	module.write_to_file(&path).unwrap();
+	let written = std::fs::read(&path);
+	std::fs::remove_file(&path).unwrap();
+
+	let session = spirv_session(&global_session);
+	let module = session
+		.load_module_from_ir_blob(
+			"serialized",
+			"serialized.slang-module",
+			&slang::Blob::from_vec(written.unwrap()),
+		)
+		.unwrap();
+	assert_eq!(module.entry_point_count(), 1);
+
+	assert!(matches!(
+		module.write_to_file("serialized\0.slang-module"),
+		Err(slang::Error::Code(slang::ErrorCode::InvalidArg))
+	));
+}
+
+#[test]
+fn binary_module_up_to_date() {
+	let global_session = slang::GlobalSession::new().unwrap();
+
+	let files = slang::MemoryFileSystem::new()
+		.with_file("helper.slang", "float helper() { return 1.0; }")
+		.with_file(
+			"main.slang",
+			"import helper; [shader(\"compute\")] [numthreads(1, 1, 1)] void main() { helper(); }",
+		);
+
+	let config =
+		spirv_config(&global_session).file_system(slang::VirtualFileSystem::new(files.clone()));
+
+	let session = global_session.create_session(&config).unwrap();
+	let module = session.load_module("main").unwrap();
+
+	// Modules pulled in through imports are loaded as well.
+	let names = session
+		.loaded_modules()
+		.map(|module| module.unwrap().name().unwrap().to_owned())
+		.collect::<Vec<_>>();
+	assert!(names.iter().any(|name| name == "main"));
+	assert!(names.iter().any(|name| name == "helper"));
+
+	let path = module.file_path().unwrap().to_owned();
+	let blob = module.serialize().unwrap();
+	assert!(session.is_binary_module_up_to_date(&path, &blob).unwrap());
+	assert!(matches!(
+		session.is_binary_module_up_to_date("main\0.slang", &blob),
+		Err(slang::Error::Code(slang::ErrorCode::InvalidArg))
+	));
+
+	files.insert(
+		"main.slang",
+		"import helper; [shader(\"compute\")] [numthreads(2, 1, 1)] void main() { helper(); }",
+	);
+
+	// Use a new session, so the edited source isn't served from the previous one.
+	let session = global_session.create_session(&config).unwrap();
+	assert!(!session.is_binary_module_up_to_date(&path, &blob).unwrap());
+}
+
+#[test]
+fn find_and_check_entry_point() {
+	let global_session = slang::GlobalSession::new().unwrap();
+
+	let session = spirv_session(&global_session);
+	let module = session
+		.load_module_from_source_string(
+			"legacy",
+			"legacy.slang",
+			"[numthreads(1, 1, 1)] void main() {}",
+		)
+		.unwrap();
+
+	assert!(module.find_entry_point_by_name("main").is_err());
+
+	let entry_point = module
+		.find_and_check_entry_point("main", slang::Stage::Compute)
+		.unwrap();
+
+	let program = session
+		.create_composite_component_type(&[module.into(), entry_point.into()])
+		.unwrap();
+
+	let linked_program = program.link().unwrap();
+	assert_eq!(linked_program.layout(0).unwrap().entry_point_count(), 1);
+}
+
+#[test]
+fn rename_entry_point() {
+	let global_session = slang::GlobalSession::new().unwrap();
+
+	let config = spirv_config(&global_session)
+		.options(slang::CompilerOptions::default().vulkan_use_entry_point_name(true));
+
+	let session = global_session.create_session(&config).unwrap();
+	let module = session
+		.load_module_from_source_string(
+			"rename",
+			"rename.slang",
+			"[shader(\"compute\")] [numthreads(1, 1, 1)] void main() {}",
+		)
+		.unwrap();
+
+	let entry_point = module.find_entry_point_by_name("main").unwrap();
+	let renamed = slang::ComponentType::from(entry_point)
+		.rename_entry_point("renamed")
+		.unwrap();
+
+	let program = session
+		.create_composite_component_type(&[module.into(), renamed])
+		.unwrap();
+
+	let linked_program = program.link().unwrap();
+	let reflection = linked_program.layout(0).unwrap();
+	let entry_point = reflection.entry_point_by_index(0).unwrap();
+	assert_eq!(entry_point.name_override(), Some("renamed"));
+
+	// The SPIR-V entry point is exported under the new name only.
+	let code = linked_program.entry_point_code(0, 0).unwrap();
+	let contains = |name: &[u8]| code.as_slice().windows(name.len()).any(|w| w == name);
+	assert!(contains(b"renamed"));
+	assert!(!contains(b"main"));
+}
+
+#[test]
+fn link_with_options() {
+	let global_session = slang::GlobalSession::new().unwrap();
+	let session = spirv_session(&global_session);
+
+	let module = session
+		.load_module_from_source_string(
+			"options",
+			"options.slang",
+			"[shader(\"compute\")] [numthreads(1, 1, 1)] void main() {}",
+		)
+		.unwrap();
+	let entry_point = module.find_entry_point_by_name("main").unwrap();
+
+	let program = session
+		.create_composite_component_type(&[module.into(), entry_point.into()])
+		.unwrap();
+
+	let plain = program.link().unwrap().entry_point_code(0, 0).unwrap();
+
+	// Options passed when linking apply to the code generated for the linked program only.
+	let options =
+		slang::CompilerOptions::default().debug_information(slang::DebugInfoLevel::Maximal);
+	let debug = program
+		.link_with_options(&options)
+		.unwrap()
+		.entry_point_code(0, 0)
+		.unwrap();
+
+	assert!(debug.as_slice().len() > plain.as_slice().len());
+	assert_eq!(
+		program
+			.link()
+			.unwrap()
+			.entry_point_code(0, 0)
+			.unwrap()
+			.as_slice(),
+		plain.as_slice()
+	);
+}
+
+#[test]
+fn specialize_entry_point() {
+	let global_session = slang::GlobalSession::new().unwrap();
+	let session = spirv_session(&global_session);
+
+	let module = session
+		.load_module_from_source_string(
+			"generic",
+			"generic.slang",
+			r#"
+			interface IScale { float scale(float x); }
+			struct Double : IScale { float scale(float x) { return x * 2.0; } }
+
+			RWStructuredBuffer<float> output;
+
+			[shader("compute")]
+			[numthreads(1, 1, 1)]
+			void main<T : IScale, let N : int>(uint3 id : SV_DispatchThreadID) {
+				T scale;
+				output[id.x] = scale.scale(float(N));
+			}
+			"#,
+		)
+		.unwrap();
+
+	let entry_point = module.find_entry_point_by_name("main").unwrap();
+
+	let program = session
+		.create_composite_component_type(&[module.into(), entry_point.into()])
+		.unwrap();
+	assert_eq!(program.specialization_param_count(), 2);
+
+	let double = program
+		.layout(0)
+		.unwrap()
+		.find_type_by_name("Double")
+		.unwrap();
+
+	let specialized = program
+		.specialize(&[
+			slang::SpecializationArg::Type(double),
+			slang::SpecializationArg::Expr("4"),
+		])
+		.unwrap();
+	assert_eq!(specialized.specialization_param_count(), 0);
+
+	let linked_program = specialized.link().unwrap();
+	assert_ne!(
+		linked_program
+			.entry_point_code(0, 0)
+			.unwrap()
+			.as_slice()
+			.len(),
+		0
+	);
+}
+
+#[test]
+fn result_file_system() {
+	let global_session = slang::GlobalSession::new().unwrap();
+
+	let config = spirv_config(&global_session).options(
+		slang::CompilerOptions::default().debug_information(slang::DebugInfoLevel::Maximal),
+	);
+
+	let session = global_session.create_session(&config).unwrap();
+	let module = session
+		.load_module_from_source_string(
+			"result",
+			"result.slang",
+			"RWStructuredBuffer<float> output; [shader(\"compute\")] [numthreads(1, 1, 1)] void main() { output[0] = 1.0; }",
+		)
+		.unwrap();
+
+	let linked_program = link_main(&session, module);
+	let code = linked_program.entry_point_code(0, 0).unwrap();
+
+	let file_system = linked_program.result_file_system(0, 0).unwrap();
+	let files = file_system.files().unwrap();
+	assert!(!files.is_empty());
+
+	// `files` starts enumerating at ".", so every file in the root must be part of it.
+	let root = file_system.path_contents(".").unwrap();
+	assert!(!root.is_empty());
+	for (path_type, name) in &root {
+		if *path_type == slang::PathType::File {
+			assert!(files.contains(name));
+		}
+	}
+
+	for path in &files {
+		assert_eq!(file_system.path_type(path).unwrap(), slang::PathType::File);
+		assert_eq!(
+			file_system.read(path).unwrap(),
+			file_system.load_file(path).unwrap().as_slice()
+		);
+	}
+
+	// The compiled code is one of the artifacts, next to the debug info.
+	assert!(
+		files
+			.iter()
+			.any(|path| file_system.read(path).unwrap() == code.as_slice())
+	);
+}
+
+struct ArchiveFileSystem;
+
+impl slang::FileSystem for ArchiveFileSystem {
+	fn load_file(&self, path: &str) -> std::io::Result<Vec<u8>> {
+		let source = match path {
+			"archive.slang" => {
+				"import helper; [shader(\"compute\")] [numthreads(1, 1, 1)] void main() { helper(); }"
+			}
+			"helper.slang" => "void helper() {}",
+			_ => return Err(std::io::ErrorKind::NotFound.into()),
+		};
+
+		Ok(source.as_bytes().to_vec())
+	}
+}
+
+#[test]
+fn virtual_file_system() {
+	let global_session = slang::GlobalSession::new().unwrap();
+
+	let file_system = slang::VirtualFileSystem::new(ArchiveFileSystem);
+	let config = spirv_config(&global_session).file_system(file_system);
+
+	let session = global_session.create_session(&config).unwrap();
+	let module = session.load_module("archive.slang").unwrap();
+	assert!(module.find_entry_point_by_name("main").is_ok());
+
+	assert!(session.load_module("missing.slang").is_err());
+}
+
+#[test]
+fn memory_file_system() {
+	use slang::FileSystem;
+
+	let file_system = slang::MemoryFileSystem::new()
+		.with_file("./shaders/../main.slang", "import lib.helper;")
+		.with_file("lib/helper.slang", "void helper() {}");
+
+	assert_eq!(file_system.paths(), ["lib/helper.slang", "main.slang"]);
+	assert_eq!(
+		file_system.load_file("main.slang").unwrap(),
+		b"import lib.helper;"
+	);
+	assert_eq!(
+		file_system.path_type("lib").unwrap(),
+		slang::PathType::Directory
+	);
+	assert_eq!(
+		file_system.path_type("lib\\helper.slang").unwrap(),
+		slang::PathType::File
+	);
+
+	assert!(file_system.remove("lib/helper.slang").is_some());
+	assert!(!file_system.contains("lib/helper.slang"));
+	assert!(file_system.path_type("lib").is_err());
+}
+
+#[test]
+fn memory_file_system_session() {
+	let global_session = slang::GlobalSession::new().unwrap();
+
+	let files = slang::MemoryFileSystem::new()
+		.with_file("lib/helper.slang", "void helper() {}")
+		.with_file(
+			"main.slang",
+			"import lib.helper; [shader(\"compute\")] [numthreads(1, 1, 1)] void main() { helper(); }",
+		);
+
+	let file_system = slang::VirtualFileSystem::new(files.clone());
+	let config = spirv_config(&global_session).file_system(file_system);
+
+	let session = global_session.create_session(&config).unwrap();
+
+	// Files added after creating the session are visible to it as well.
+	files.insert("late.slang", "void late() {}");
+
+	let module = session.load_module("main").unwrap();
+	let linked_program = link_main(&session, module);
+	assert_ne!(
+		linked_program
+			.entry_point_code(0, 0)
+			.unwrap()
+			.as_slice()
+			.len(),
+		0
+	);
+
+	assert!(session.load_module("late").is_ok());
+}
+
+#[test]
+fn blob_from_vec() {
+	let blob = slang::Blob::from_vec(b"slang".to_vec());
+	let clone = blob.clone();
+	drop(blob);
+
+	assert_eq!(clone.as_slice(), b"slang");
+	assert_eq!(clone.as_str().unwrap(), "slang");
+	assert_eq!(slang::Blob::from_bytes(&[]).as_slice().len(), 0);
+}
+
+#[test]
+fn load_module_from_source_bytes() {
+	let global_session = slang::GlobalSession::new().unwrap();
+
+	let session = spirv_session(&global_session);
+
+	// The string literal holds raw NUL and control bytes, like an embedded binary table.
+	let source = "RWStructuredBuffer<uint> output; [shader(\"compute\")] [numthreads(1, 1, 1)] void main() { output[0] = getStringHash(\"\0\x01\x02\"); }";
+	assert!(
+		session
+			.load_module_from_source_string("table", "table.slang", source)
+			.is_err()
+	);
+
+	let module = session
+		.load_module_from_source_bytes("table", "table.slang", source.as_bytes())
+		.unwrap();
+	assert_eq!(module.name(), Some("table"));
+
+	let linked_program = link_main(&session, module);
+	assert_ne!(
+		linked_program
+			.entry_point_code(0, 0)
+			.unwrap()
+			.as_slice()
+			.len(),
+		0
+	);
+}
+
+#[test]
+fn parse_diagnostics() {
+	let text = "\
+shaders/test.slang(8): error 30015: undefined identifier 'foo'.
+	foo(index);
+	^~~
+shaders/test.slang(3): warning 15205: unused variable 'bar'.
+(0): note: see declaration of 'baz'
+error 1: cannot open file 'missing.slang'.
+";
+
+	let diagnostics = slang::Diagnostic::parse(text);
+	assert_eq!(diagnostics.len(), 4);
+
+	assert_eq!(
+		diagnostics[0],
+		slang::Diagnostic {
+			file: Some("shaders/test.slang".into()),
+			line: Some(8),
+			column: Some(2),
+			severity: slang::Severity::Error,
+			code: Some(30015),
+			message: "undefined identifier 'foo'.".into(),
+		}
+	);
+
+	assert_eq!(diagnostics[1].severity, slang::Severity::Warning);
+	assert_eq!(diagnostics[1].column, None);
+
+	assert_eq!(diagnostics[2].file, None);
+	assert_eq!(diagnostics[2].severity, slang::Severity::Note);
+	assert_eq!(diagnostics[2].code, None);
+
+	assert_eq!(diagnostics[3].line, None);
+	assert_eq!(diagnostics[3].code, Some(1));
+	assert_eq!(diagnostics[3].message, "cannot open file 'missing.slang'.");
+
+	let error = slang::Error::Blob(slang::Blob::from_bytes(text.as_bytes()));
+	assert_eq!(error.diagnostics(), diagnostics);
+
+	let error = slang::Error::Code(slang::ErrorCode::from_raw(0x82000005_u32 as _));
+	assert!(matches!(
+		error,
+		slang::Error::Code(slang::ErrorCode::NotFound)
+	));
+	assert!(error.diagnostics().is_empty());
+}
+
+#[test]
+fn warnings_on_success() {
+	let global_session = slang::GlobalSession::new().unwrap();
+
+	let session = spirv_session(&global_session);
+
+	// The implicit float to int conversion produces a warning, not an error.
+	let result = session
+		.load_module_from_source_string_with_diagnostics(
+			"warning",
+			"warning.slang",
+			"[shader(\"compute\")] [numthreads(1, 1, 1)] void main() { int x = 1.5; }",
+		)
+		.unwrap();
+
+	let diagnostics = result.parsed_diagnostics();
+	assert!(
+		diagnostics
+			.iter()
+			.any(|diagnostic| diagnostic.severity == slang::Severity::Warning)
+	);
+
+	let module = result.value;
+	let entry_point = module.find_entry_point_by_name("main").unwrap();
+
+	let program = session
+		.create_composite_component_type(&[module.into(), entry_point.into()])
+		.unwrap();
+
+	let linked_program = program.link_with_diagnostics().unwrap().value;
+	assert_ne!(
+		linked_program
+			.entry_point_code(0, 0)
+			.unwrap()
+			.as_slice()
+			.len(),
+		0
+	);
+}
+
+#[test]
+fn diagnostic_callback() {
+	use std::sync::{Arc, Mutex};
+
+	let global_session = slang::GlobalSession::new().unwrap();
+
+	let session = spirv_session(&global_session);
+
+	let received = Arc::new(Mutex::new(Vec::new()));
+	let handler = session.on_diagnostic({
+		let received = received.clone();
+		move |diagnostic| received.lock().unwrap().push(diagnostic.to_string())
+	});
+
+	let source = "[shader(\"compute\")] [numthreads(1, 1, 1)] void main() { undefined(); }";
+	assert!(
+		session
+			.load_module_from_source_string("broken", "broken.slang", source)
+			.is_err()
+	);
+
+	let count = received.lock().unwrap().len();
+	assert_ne!(count, 0);
+	assert!(received.lock().unwrap()[0].starts_with("broken.slang("));
+
+	drop(handler);
+
+	assert!(
+		session
+			.load_module_from_source_string("broken2", "broken2.slang", source)
+			.is_err()
+	);
+	assert_eq!(received.lock().unwrap().len(), count);
+}
+
+/// Stand-ins for Slang objects that fail without producing diagnostics or output objects.
+mod mock {
+	use std::ffi::{c_char, c_int, c_void};
+	use std::marker::PhantomData;
+	use std::ptr::{NonNull, null, null_mut};
+
+	use crate::com::{self, Implements, Object};
+	use crate::{Blob, ComponentType, IUnknown, Interface, Module, Session, UUID, sys};
+
+	struct Mock;
+
+	unsafe impl Implements for Mock {
+		const INTERFACES: &'static [UUID] =
+			&[IUnknown::IID, Session::IID, ComponentType::IID, Module::IID];
+	}
+
+	pub fn session() -> Session {
+		Session(Object::allocate(&SESSION_VTABLE, Mock))
+	}
+
+	pub fn component_type() -> ComponentType<'static> {
+		ComponentType(Object::allocate(&COMPONENT_TYPE_VTABLE, Mock), PhantomData)
+	}
+
+	pub fn module() -> Module<'static> {
+		Module(Object::allocate(&MODULE_VTABLE, Mock), PhantomData)
+	}
+
+	static SESSION_VTABLE: sys::ISessionVtable = sys::ISessionVtable {
+		_base: com::unknown_vtable::<Mock>(),
+		getGlobalSession: get_global_session,
+		loadModule: load_module,
+		loadModuleFromSource: load_module_from_source,
+		createCompositeComponentType: create_composite_component_type,
+		specializeType: specialize_type,
+		getTypeLayout: get_type_layout,
+		getContainerType: get_container_type,
+		getDynamicType: get_dynamic_type,
+		getTypeRTTIMangledName: get_type_rtti_mangled_name,
+		getTypeConformanceWitnessMangledName: get_type_conformance_witness_mangled_name,
+		getTypeConformanceWitnessSequentialID: get_type_conformance_witness_sequential_id,
+		createCompileRequest: create_compile_request,
+		createTypeConformanceComponentType: create_type_conformance_component_type,
+		loadModuleFromIRBlob: load_module_from_source,
+		getLoadedModuleCount: get_loaded_module_count,
+		getLoadedModule: get_loaded_module,
+		isBinaryModuleUpToDate: is_binary_module_up_to_date,
+		loadModuleFromSourceString: load_module_from_source_string,
+	};
+
+	static COMPONENT_TYPE_VTABLE: sys::IComponentTypeVtable = component_type_vtable();
+
+	static MODULE_VTABLE: sys::IModuleVtable = sys::IModuleVtable {
+		_base: component_type_vtable(),
+		findEntryPointByName: find_entry_point_by_name,
+		getDefinedEntryPointCount: get_defined_entry_point_count,
+		getDefinedEntryPoint: get_defined_entry_point,
+		serialize,
+		writeToFile: write_to_file,
+		getName: get_name,
+		getFilePath: get_name,
+		getUniqueIdentity: get_name,
+		findAndCheckEntryPoint: find_and_check_entry_point,
+		getDependencyFileCount: get_dependency_file_count,
+		getDependencyFilePath: get_dependency_file_path,
+		getModuleReflection: get_module_reflection,
+	};
+
+	const fn component_type_vtable() -> sys::IComponentTypeVtable {
+		sys::IComponentTypeVtable {
+			_base: com::unknown_vtable::<Mock>(),
+			getSession: get_session,
+			getLayout: get_layout,
+			getSpecializationParamCount: get_specialization_param_count,
+			getEntryPointCode: get_entry_point_code,
+			getResultAsFileSystem: get_result_as_file_system,
+			getEntryPointHash: get_entry_point_hash,
+			specialize,
+			link,
+			getEntryPointHostCallable: get_entry_point_host_callable,
+			renameEntryPoint: rename_entry_point,
+			linkWithOptions: link_with_options,
+			getTargetCode: get_target_code,
+			getTargetMetadata: get_target_metadata,
+			getEntryPointMetadata: get_entry_point_metadata,
+		}
+	}
+
+	unsafe extern "C" fn get_global_session(_this: *mut c_void) -> *mut sys::slang_IGlobalSession {
+		null_mut()
+	}
+
+	unsafe extern "C" fn load_module(
+		_this: *mut c_void,
+		_module_name: *const c_char,
+		_out_diagnostics: *mut *mut sys::ISlangBlob,
+	) -> *mut sys::slang_IModule {
+		null_mut()
+	}
+
+	unsafe extern "C" fn load_module_from_source(
+		_this: *mut c_void,
+		_module_name: *const c_char,
+		_path: *const c_char,
+		_source: *mut sys::ISlangBlob,
+		_out_diagnostics: *mut *mut sys::ISlangBlob,
+	) -> *mut sys::slang_IModule {
+		null_mut()
+	}
+
+	unsafe extern "C" fn load_module_from_source_string(
+		_this: *mut c_void,
+		_module_name: *const c_char,
+		_path: *const c_char,
+		_string: *const c_char,
+		_out_diagnostics: *mut *mut sys::ISlangBlob,
+	) -> *mut sys::slang_IModule {
+		null_mut()
+	}
+
+	unsafe extern "C" fn create_composite_component_type(
+		_this: *mut c_void,
+		_component_types: *const *const sys::slang_IComponentType,
+		_component_type_count: sys::SlangInt,
+		_out_composite_component_type: *mut *mut sys::slang_IComponentType,
+		_out_diagnostics: *mut *mut sys::ISlangBlob,
+	) -> sys::SlangResult {
+		crate::OK
+	}
+
+	unsafe extern "C" fn specialize_type(
+		_this: *mut c_void,
+		_type: *mut sys::slang_TypeReflection,
+		_specialization_args: *const sys::slang_SpecializationArg,
+		_specialization_arg_count: sys::SlangInt,
+		_out_diagnostics: *mut *mut sys::ISlangBlob,
+	) -> *mut sys::slang_TypeReflection {
+		null_mut()
+	}
+
+	unsafe extern "C" fn get_type_layout(
+		_this: *mut c_void,
+		_type: *mut sys::slang_TypeReflection,
+		_target_index: sys::SlangInt,
+		_rules: sys::slang_LayoutRules,
+		_out_diagnostics: *mut *mut sys::ISlangBlob,
+	) -> *mut sys::slang_TypeLayoutReflection {
+		null_mut()
+	}
+
+	unsafe extern "C" fn get_container_type(
+		_this: *mut c_void,
+		_element_type: *mut sys::slang_TypeReflection,
+		_container_type: sys::slang_ContainerType,
+		_out_diagnostics: *mut *mut sys::ISlangBlob,
+	) -> *mut sys::slang_TypeReflection {
+		null_mut()
+	}
+
+	unsafe extern "C" fn get_dynamic_type(_this: *mut c_void) -> *mut sys::slang_TypeReflection {
+		null_mut()
+	}
+
+	unsafe extern "C" fn get_type_rtti_mangled_name(
+		_this: *mut c_void,
+		_type: *mut sys::slang_TypeReflection,
+		_out_name_blob: *mut *mut sys::ISlangBlob,
+	) -> sys::SlangResult {
+		crate::E_NOT_IMPLEMENTED
+	}
+
+	unsafe extern "C" fn get_type_conformance_witness_mangled_name(
+		_this: *mut c_void,
+		_type: *mut sys::slang_TypeReflection,
+		_interface_type: *mut sys::slang_TypeReflection,
+		_out_name_blob: *mut *mut sys::ISlangBlob,
+	) -> sys::SlangResult {
+		crate::E_NOT_IMPLEMENTED
+	}
+
+	unsafe extern "C" fn get_type_conformance_witness_sequential_id(
+		_this: *mut c_void,
+		_type: *mut sys::slang_TypeReflection,
+		_interface_type: *mut sys::slang_TypeReflection,
+		_out_id: *mut u32,
+	) -> sys::SlangResult {
+		crate::E_NOT_IMPLEMENTED
+	}
+
+	unsafe extern "C" fn create_compile_request(
+		_this: *mut c_void,
+		_out_compile_request: *mut *mut sys::slang_ICompileRequest,
+	) -> sys::SlangResult {
+		crate::E_NOT_IMPLEMENTED
+	}
+
+	unsafe extern "C" fn create_type_conformance_component_type(
+		_this: *mut c_void,
+		_type: *mut sys::slang_TypeReflection,
+		_interface_type: *mut sys::slang_TypeReflection,
+		_out_conformance: *mut *mut sys::slang_ITypeConformance,
+		_conformance_id_override: sys::SlangInt,
+		_out_diagnostics: *mut *mut sys::ISlangBlob,
+	) -> sys::SlangResult {
+		crate::E_NOT_IMPLEMENTED
+	}
+
+	unsafe extern "C" fn get_loaded_module_count(_this: *mut c_void) -> sys::SlangInt {
+		1
+	}
+
+	unsafe extern "C" fn get_loaded_module(
+		_this: *mut c_void,
+		_index: sys::SlangInt,
+	) -> *mut sys::slang_IModule {
+		null_mut()
+	}
+
+	unsafe extern "C" fn is_binary_module_up_to_date(
+		_this: *mut c_void,
+		_module_path: *const c_char,
+		_binary_module_blob: *mut sys::ISlangBlob,
+	) -> bool {
+		false
+	}
+
+	unsafe extern "C" fn get_session(_this: *mut c_void) -> *mut sys::slang_ISession {
+		null_mut()
+	}
+
+	/// `getLayout` succeeds with `WARNING` for this target, but its layout must not be dereferenced.
+	pub const WARNING_TARGET: i64 = 1;
+	pub const WARNING: &str = "mock.slang(1): warning 1: mock warning.\n";
+
+	unsafe extern "C" fn get_layout(
+		_this: *mut c_void,
+		target_index: sys::SlangInt,
+		out_diagnostics: *mut *mut sys::ISlangBlob,
+	) -> *mut sys::slang_ProgramLayout {
+		if target_index != WARNING_TARGET {
+			return null_mut();
+		}
+
+		unsafe { *out_diagnostics = com::into_raw(Blob::from_bytes(WARNING.as_bytes())) };
+		NonNull::dangling().as_ptr()
+	}
+
+	unsafe extern "C" fn get_specialization_param_count(_this: *mut c_void) -> sys::SlangInt {
+		0
+	}
+
+	unsafe extern "C" fn get_entry_point_code(
+		_this: *mut c_void,
+		_entry_point_index: sys::SlangInt,
+		_target_index: sys::SlangInt,
+		_out_code: *mut *mut sys::ISlangBlob,
+		_out_diagnostics: *mut *mut sys::ISlangBlob,
+	) -> sys::SlangResult {
+		crate::OK
+	}
+
+	unsafe extern "C" fn get_result_as_file_system(
+		_this: *mut c_void,
+		_entry_point_index: sys::SlangInt,
+		_target_index: sys::SlangInt,
+		_out_file_system: *mut *mut sys::ISlangMutableFileSystem,
+	) -> sys::SlangResult {
+		crate::OK
+	}
+
+	/// `getEntryPointHash` returns a digest of the wrong size for this target.
+	pub const SHORT_HASH_TARGET: i64 = 1;
+
+	unsafe extern "C" fn get_entry_point_hash(
+		_this: *mut c_void,
+		_entry_point_index: sys::SlangInt,
+		target_index: sys::SlangInt,
+		out_hash: *mut *mut sys::ISlangBlob,
+	) {
+		if target_index == SHORT_HASH_TARGET {
+			unsafe { *out_hash = com::into_raw(Blob::from_bytes(&[0; 4])) };
+		}
+	}
+
+	unsafe extern "C" fn specialize(
+		_this: *mut c_void,
+		_specialization_args: *const sys::slang_SpecializationArg,
+		_specialization_arg_count: sys::SlangInt,
+		_out_specialized_component_type: *mut *mut sys::slang_IComponentType,
+		_out_diagnostics: *mut *mut sys::ISlangBlob,
+	) -> sys::SlangResult {
+		crate::OK
+	}
+
+	unsafe extern "C" fn link(
+		_this: *mut c_void,
+		_out_linked_component_type: *mut *mut sys::slang_IComponentType,
+		_out_diagnostics: *mut *mut sys::ISlangBlob,
+	) -> sys::SlangResult {
+		crate::OK
+	}
+
+	unsafe extern "C" fn get_entry_point_host_callable(
+		_this: *mut c_void,
+		_entry_point_index: c_int,
+		_target_index: c_int,
+		_out_shared_library: *mut *mut sys::ISlangSharedLibrary,
+		_out_diagnostics: *mut *mut sys::ISlangBlob,
+	) -> sys::SlangResult {
+		crate::E_NOT_IMPLEMENTED
+	}
+
+	unsafe extern "C" fn rename_entry_point(
+		_this: *mut c_void,
+		_new_name: *const c_char,
+		_out_entry_point: *mut *mut sys::slang_IComponentType,
+	) -> sys::SlangResult {
+		crate::E_FAIL
+	}
+
+	unsafe extern "C" fn link_with_options(
+		_this: *mut c_void,
+		_out_linked_component_type: *mut *mut sys::slang_IComponentType,
+		_compiler_option_entry_count: u32,
+		_compiler_option_entries: *mut sys::slang_CompilerOptionEntry,
+		_out_diagnostics: *mut *mut sys::ISlangBlob,
+	) -> sys::SlangResult {
+		crate::OK
+	}
+
+	unsafe extern "C" fn get_target_code(
+		_this: *mut c_void,
+		_target_index: sys::SlangInt,
+		_out_code: *mut *mut sys::ISlangBlob,
+		_out_diagnostics: *mut *mut sys::ISlangBlob,
+	) -> sys::SlangResult {
+		crate::E_FAIL
+	}
+
+	unsafe extern "C" fn get_target_metadata(
+		_this: *mut c_void,
+		_target_index: sys::SlangInt,
+		_out_metadata: *mut *mut sys::slang_IMetadata,
+		_out_diagnostics: *mut *mut sys::ISlangBlob,
+	) -> sys::SlangResult {
+		crate::OK
+	}
+
+	unsafe extern "C" fn get_entry_point_metadata(
+		_this: *mut c_void,
+		_entry_point_index: sys::SlangInt,
+		_target_index: sys::SlangInt,
+		_out_metadata: *mut *mut sys::slang_IMetadata,
+		_out_diagnostics: *mut *mut sys::ISlangBlob,
+	) -> sys::SlangResult {
+		crate::OK
+	}
+
+	unsafe extern "C" fn find_entry_point_by_name(
+		_this: *mut c_void,
+		_name: *const c_char,
+		_out_entry_point: *mut *mut sys::slang_IEntryPoint,
+	) -> sys::SlangResult {
+		crate::E_FAIL
+	}
+
+	unsafe extern "C" fn get_defined_entry_point_count(_this: *mut c_void) -> sys::SlangInt32 {
+		1
+	}
+
+	unsafe extern "C" fn get_defined_entry_point(
+		_this: *mut c_void,
+		_index: sys::SlangInt32,
+		_out_entry_point: *mut *mut sys::slang_IEntryPoint,
+	) -> sys::SlangResult {
+		crate::OK
+	}
+
+	unsafe extern "C" fn serialize(
+		_this: *mut c_void,
+		_out_serialized_blob: *mut *mut sys::ISlangBlob,
+	) -> sys::SlangResult {
+		crate::OK
+	}
+
+	unsafe extern "C" fn write_to_file(
+		_this: *mut c_void,
+		_file_name: *const c_char,
+	) -> sys::SlangResult {
+		crate::E_NOT_IMPLEMENTED
+	}
+
+	unsafe extern "C" fn get_name(_this: *mut c_void) -> *const c_char {
+		null()
+	}
+
+	unsafe extern "C" fn find_and_check_entry_point(
+		_this: *mut c_void,
+		_name: *const c_char,
+		_stage: sys::SlangStage,
+		_out_entry_point: *mut *mut sys::slang_IEntryPoint,
+		_out_diagnostics: *mut *mut sys::ISlangBlob,
+	) -> sys::SlangResult {
+		crate::OK
+	}
+
+	unsafe extern "C" fn get_dependency_file_count(_this: *mut c_void) -> sys::SlangInt32 {
+		0
+	}
+
+	unsafe extern "C" fn get_dependency_file_path(
+		_this: *mut c_void,
+		_index: sys::SlangInt32,
+	) -> *const c_char {
+		null()
+	}
+
+	unsafe extern "C" fn get_module_reflection(
+		_this: *mut c_void,
+	) -> *mut sys::slang_DeclReflection {
+		null_mut()
+	}
+}
+
+#[test]
+fn null_outputs() {
+	use slang::{Error, ErrorCode};
+
+	let session = mock::session();
+
+	assert!(matches!(session.load_module("missing"), Err(Error::Null)));
+	assert!(matches!(
+		session.load_module_from_source_string("missing", "missing.slang", ""),
+		Err(Error::Null)
+	));
+	assert!(matches!(
+		session.load_module_from_source_bytes("missing", "missing.slang", b""),
+		Err(Error::Null)
+	));
+	assert!(matches!(
+		session.load_module_from_ir_blob("missing", "missing.slang", &slang::Blob::from_bytes(b"")),
+		Err(Error::Null)
+	));
+	assert!(matches!(
+		session.create_composite_component_type(&[]),
+		Err(Error::Null)
+	));
+
+	assert_eq!(session.loaded_modules().len(), 1);
+	assert!(
+		session
+			.loaded_modules()
+			.all(|module| matches!(module, Err(Error::Null)))
+	);
+
+	let module = mock::module();
+
+	assert!(matches!(
+		module.find_entry_point_by_name("main"),
+		Err(Error::Code(ErrorCode::Fail))
+	));
+
+	assert_eq!(module.entry_points().len(), 1);
+	assert!(
+		module
+			.entry_points()
+			.all(|entry_point| matches!(entry_point, Err(Error::Null)))
+	);
+	assert!(matches!(
+		module.find_and_check_entry_point("main", slang::Stage::Compute),
+		Err(Error::Null)
+	));
+	assert!(matches!(module.serialize(), Err(Error::Null)));
+
+	let program = mock::component_type();
+
+	assert!(matches!(program.layout(0), Err(Error::Null)));
+	assert!(matches!(program.link(), Err(Error::Null)));
+	assert!(matches!(
+		program.link_with_options(&slang::CompilerOptions::default()),
+		Err(Error::Null)
+	));
+	assert!(matches!(program.specialize(&[]), Err(Error::Null)));
+	assert!(matches!(program.entry_point_code(0, 0), Err(Error::Null)));
+	assert!(matches!(program.entry_point_hash(0, 0), Err(Error::Null)));
+	assert!(matches!(
+		program.entry_point_hash_bytes(0, 0),
+		Err(Error::Null)
+	));
+	assert!(matches!(
+		program.entry_point_hash_bytes(0, mock::SHORT_HASH_TARGET),
+		Err(Error::UnexpectedSize {
+			expected: 20,
+			actual: 4
+		})
+	));
+	assert!(matches!(program.result_file_system(0, 0), Err(Error::Null)));
+	assert!(matches!(program.target_metadata(0), Err(Error::Null)));
+	assert!(matches!(
+		program.entry_point_metadata(0, 0),
+		Err(Error::Null)
+	));
+
+	// Failures without diagnostics keep their result code.
+	assert!(matches!(
+		program.target_code(0),
+		Err(Error::Code(ErrorCode::Fail))
+	));
+	assert!(matches!(
+		program.rename_entry_point("renamed"),
+		Err(Error::Code(ErrorCode::Fail))
+	));
+}
+
+#[test]
+fn layout_diagnostics() {
+	let program = mock::component_type();
+
+	let result = program
+		.layout_with_diagnostics(mock::WARNING_TARGET)
+		.unwrap();
+	assert_eq!(
+		result.diagnostics.as_ref().unwrap().as_str().unwrap(),
+		mock::WARNING
+	);
+	assert_eq!(
+		result.parsed_diagnostics()[0].severity,
+		slang::Severity::Warning
+	);
+}
+
+#[test]
+fn interior_nul_strings() {
+	use slang::{Error, ErrorCode};
+
+	let global_session = slang::GlobalSession::new().unwrap();
+	assert!(matches!(
+		global_session.find_profile("glsl\0_450"),
+		Err(Error::Code(ErrorCode::InvalidArg))
+	));
+	assert!(matches!(
+		global_session.find_capability("spirv\0_1_5"),
+		Err(Error::Code(ErrorCode::InvalidArg))
+	));
+	assert!(
+		global_session
+			.find_profile("not_a_profile")
+			.unwrap()
+			.is_unknown()
+	);
+	assert!(global_session.build_tag_string().is_some());
+
+	let invalid_options = || slang::CompilerOptions::default().include("sha\0ders");
+
+	assert!(matches!(
+		global_session.create_session(&spirv_config(&global_session).options(invalid_options())),
+		Err(Error::Code(ErrorCode::InvalidArg))
+	));
+	assert!(matches!(
+		global_session.create_session(
+			&slang::SessionConfig::default()
+				.target(slang::TargetConfig::default().options(invalid_options()))
+		),
+		Err(Error::Code(ErrorCode::InvalidArg))
+	));
+	assert!(matches!(
+		global_session
+			.session_desc_digest(&spirv_config(&global_session).options(invalid_options())),
+		Err(Error::Code(ErrorCode::InvalidArg))
+	));
+
+	let session = spirv_session(&global_session);
+	assert!(matches!(
+		session.load_module("shader\0.slang"),
+		Err(Error::Code(ErrorCode::InvalidArg))
+	));
+
+	let module = session
+		.load_module_from_source_string(
+			"strings",
+			"strings.slang",
+			"[shader(\"compute\")] [numthreads(1, 1, 1)] void main() {}",
+		)
+		.unwrap();
+
+	assert!(matches!(
+		module.find_entry_point_by_name("ma\0in"),
+		Err(Error::Code(ErrorCode::InvalidArg))
+	));
+	assert!(matches!(
+		module.find_and_check_entry_point("ma\0in", slang::Stage::Compute),
+		Err(Error::Code(ErrorCode::InvalidArg))
+	));
+
+	let program = slang::ComponentType::from(module);
+	assert!(matches!(
+		program.link_with_options(&invalid_options()),
+		Err(Error::Code(ErrorCode::InvalidArg))
+	));
+
+	let program = program.link().unwrap();
+	let reflection = program.layout(0).unwrap();
+	assert!(reflection.find_type_by_name("flo\0at").is_none());
+	assert!(reflection.find_function_by_name("ma\0in").is_none());
+}
+
+#[test]
+fn session_pool() {
+	fn assert_send_sync<T: Send + Sync>() {}
+	assert_send_sync::<slang::SessionPool>();
+	assert_send_sync::<slang::CompilerOptions>();
+	assert_send_sync::<slang::VirtualFileSystem>();
+	assert_send_sync::<slang::SessionConfig>();
+
+	assert!(slang::SessionPool::new(0, |_| unreachable!()).is_none());
+
+	let global_session = slang::GlobalSession::new().unwrap();
+	let pool = slang::SessionPool::with_config(2, spirv_config(&global_session)).unwrap();
+
+	assert_eq!(pool.worker_count(), 2);
+
+	let jobs = (0..4)
+		.map(|i| {
+			pool.execute(move |session| {
+				let source = format!(
+					"[shader(\"compute\")] [numthreads({}, 1, 1)] void main() {{}}",
+					i + 1
+				);
+
+				let module = session
+					.load_module_from_source_string(
+						&format!("job{i}"),
+						&format!("job{i}.slang"),
+						&source,
+					)
+					.unwrap();
+				let linked_program = link_main(session, module);
+				let code = linked_program.entry_point_code(0, 0).unwrap();
+				code.as_slice().to_vec()
+			})
+		})
+		.collect::<Vec<_>>();
+
+	for job in jobs {
+		assert_ne!(job.wait().unwrap().len(), 0);
+	}
+}
+
+#[test]
+fn compiler_options_clone() {
+	let options = slang::CompilerOptions::default()
+		.macro_define("A", "1")
+		.optimization(slang::OptimizationLevel::High)
+		.include("shaders");
+
+	let cloned = options.clone();
+	drop(options);
+
+	let strings = cloned
+		.options
+		.iter()
+		.filter(|entry| entry.value.kind == slang::sys::slang_CompilerOptionValueKind::String)
+		.flat_map(|entry| [entry.value.stringValue0, entry.value.stringValue1])
+		.filter(|ptr| !ptr.is_null())
+		.map(|ptr| {
+			assert!(cloned.strings.iter().any(|s| s.as_ptr() == ptr));
+			unsafe { std::ffi::CStr::from_ptr(ptr) }.to_str().unwrap()
+		})
+		.collect::<Vec<_>>();
+
+	assert_eq!(strings, ["A", "1", "shaders"]);
+}
+
+#[test]
+fn compiler_option_strings() {
+	use slang::{Error, ErrorCode};
+
+	let options = slang::CompilerOptions::default().macro_define("A", "1");
+	assert_eq!(options.options.len(), 1);
+	assert_eq!(options.strings.len(), 2);
+	assert!(options.check().is_ok());
+
+	for options in [
+		slang::CompilerOptions::default().macro_define("A\0", "1"),
+		slang::CompilerOptions::default().macro_define("A", "1\0"),
+		slang::CompilerOptions::default()
+			.include("sha\0ders")
+			.optimization(slang::OptimizationLevel::High),
+	] {
+		// No string is kept for an argument that couldn't be converted.
+		assert!(options.strings.is_empty());
+		assert!(matches!(
+			options.check(),
+			Err(Error::Code(ErrorCode::InvalidArg))
+		));
+		assert!(matches!(
+			options.clone().check(),
+			Err(Error::Code(ErrorCode::InvalidArg))
+		));
+	}
+}
+
+#[test]
+fn session_config() {
+	let global_session = slang::GlobalSession::new().unwrap();
+
+	let config = spirv_config(&global_session)
+		.search_path("shaders")
+		.unwrap()
+		.options(slang::CompilerOptions::default().matrix_layout_row(true));
+
+	assert!(matches!(
+		config.clone().search_path("sha\0ders"),
+		Err(slang::Error::Code(slang::ErrorCode::InvalidArg))
+	));
+
+	// The same config can create any number of sessions.
+	for config in [config.clone(), config] {
+		let session = global_session.create_session(&config).unwrap();
+		let module = session.load_module("test.slang").unwrap();
+		let linked_program = link_main(&session, module);
+		assert_ne!(
+			linked_program
+				.entry_point_code(0, 0)
+				.unwrap()
+				.as_slice()
+				.len(),
+			0
+		);
+	}
+}
+
+#[test]
+fn session_desc_round_trip() {
+	use slang::{AsSessionDesc, Interface};
+
+	fn macros(desc: &slang::SessionDesc) -> Vec<(String, String)> {
+		let to_str = |ptr| {
+			unsafe { std::ffi::CStr::from_ptr(ptr) }
+				.to_str()
+				.unwrap()
+				.to_owned()
+		};
+		let macros = unsafe {
+			std::slice::from_raw_parts(desc.preprocessorMacros, desc.preprocessorMacroCount as _)
+		};
+		macros
+			.iter()
+			.map(|m| (to_str(m.name), to_str(m.value)))
+			.collect()
+	}
+
+	let file_system = slang::VirtualFileSystem::new(slang::MemoryFileSystem::default());
+	let macros_in = slang::PreprocessorMacros::default()
+		.define("A", "1")
+		.unwrap()
+		.define("B", "")
+		.unwrap();
+	assert!(matches!(
+		macros_in.clone().define("C\0", "1"),
+		Err(slang::Error::Code(slang::ErrorCode::InvalidArg))
+	));
+
+	// Slang defines no session flags yet, so any bits are passed through as they are.
+	let flags = slang::SessionFlags::from_bits_retain(1 << 3);
+	assert!(slang::SessionFlags::default().is_empty());
+
+	let desc = slang::SessionDesc::default()
+		.preprocessor_macros(&macros_in)
+		.flags(flags)
+		.default_matrix_layout_mode(slang::MatrixLayoutMode::RowMajor)
+		.file_system(&file_system)
+		.allow_glsl_syntax(true)
+		.enable_effect_annotations(true)
+		.skip_spirv_validation(true);
+
+	assert_eq!(
+		macros(&desc),
+		[
+			("A".to_owned(), "1".to_owned()),
+			("B".to_owned(), "".to_owned())
+		]
+	);
+	assert_eq!(desc.flags, flags.bits());
+	assert_eq!(
+		desc.defaultMatrixLayoutMode,
+		slang::MatrixLayoutMode::RowMajor
+	);
+	assert_eq!(desc.fileSystem, unsafe { file_system.as_raw() });
+	assert!(desc.allowGLSLSyntax);
+	assert!(desc.enableEffectAnnotations);
+	assert!(desc.skipSPIRVValidation);
+
+	let config = slang::SessionConfig::default()
+		.preprocessor_macros(macros_in.clone())
+		.flags(flags)
+		.default_matrix_layout_mode(slang::MatrixLayoutMode::RowMajor)
+		.file_system(file_system.clone())
+		.allow_glsl_syntax(true)
+		.enable_effect_annotations(true)
+		.skip_spirv_validation(true)
+		.clone();
+
+	config.with_session_desc(|config_desc| {
+		assert_eq!(macros(config_desc), macros(&desc));
+		assert_eq!(config_desc.flags, desc.flags);
+		assert_eq!(
+			config_desc.defaultMatrixLayoutMode,
+			desc.defaultMatrixLayoutMode
+		);
+		assert_eq!(config_desc.fileSystem, desc.fileSystem);
+		assert!(config_desc.allowGLSLSyntax);
+		assert!(config_desc.enableEffectAnnotations);
+		assert!(config_desc.skipSPIRVValidation);
+	});
+
+	// Search paths are passed on byte for byte, even when they aren't valid UTF-8.
+	#[cfg(unix)]
+	{
+		use std::os::unix::ffi::OsStrExt;
+
+		let path = std::ffi::OsStr::from_bytes(b"sh\xe4ders");
+		let config = slang::SessionConfig::default().search_path(path).unwrap();
+
+		config.with_session_desc(|config_desc| {
+			assert_eq!(config_desc.searchPathCount, 1);
+
+			let search_path = unsafe { std::ffi::CStr::from_ptr(*config_desc.searchPaths) };
+			assert_eq!(search_path.to_bytes(), path.as_bytes());
+		});
+	}
+}
+
+#[test]
+fn target_desc_round_trip() {
+	use slang::{AsSessionDesc, TargetFlags};
+
+	let mut flags = TargetFlags::GENERATE_SPIRV_DIRECTLY | TargetFlags::DUMP_IR;
+	flags.remove(TargetFlags::DUMP_IR);
+	flags |= TargetFlags::PARAMETER_BLOCKS_USE_REGISTER_SPACES;
+
+	assert!(flags.contains(TargetFlags::GENERATE_SPIRV_DIRECTLY));
+	assert!(!flags.contains(TargetFlags::DUMP_IR));
+	assert_eq!(flags.bits(), 1 << 10 | 1 << 4);
+	assert!(TargetFlags::default().is_empty());
+
+	let desc = slang::TargetDesc::default()
+		.flags(flags)
+		.floating_point_mode(slang::FloatingPointMode::Fast)
+		.line_directive_mode(slang::LineDirectiveMode::Glsl)
+		.force_glsl_scalar_buffer_layout(true);
+
+	assert_eq!(desc.flags, flags.bits());
+	assert_eq!(desc.floatingPointMode, slang::FloatingPointMode::Fast);
+	assert_eq!(desc.lineDirectiveMode, slang::LineDirectiveMode::Glsl);
+	assert!(desc.forceGLSLScalarBufferLayout);
+
+	let config = slang::SessionConfig::default().target(
+		slang::TargetConfig::default()
+			.flags(flags)
+			.floating_point_mode(slang::FloatingPointMode::Fast)
+			.line_directive_mode(slang::LineDirectiveMode::Glsl)
+			.force_glsl_scalar_buffer_layout(true),
+	);
+
+	config.with_session_desc(|session_desc| {
+		assert_eq!(session_desc.targetCount, 1);
+
+		let target = unsafe { &*session_desc.targets };
+		assert_eq!(target.flags, desc.flags);
+		assert_eq!(target.floatingPointMode, desc.floatingPointMode);
+		assert_eq!(target.lineDirectiveMode, desc.lineDirectiveMode);
+		assert!(target.forceGLSLScalarBufferLayout);
+	});
+}
+
+#[test]
+fn compiler_option_coverage() {
+	use slang::CompilerOptionName as Name;
+
+	// Only used by the slangc command line, or applied to the global session rather than a session.
+	let command_line_only = [
+		Name::DepFile,
+		Name::Help,
+		Name::HelpStyle,
+		Name::Output,
+		Name::Version,
+		Name::InputFilesRemain,
+		Name::SourceEmbedStyle,
+		Name::SourceEmbedName,
+		Name::SourceEmbedLanguage,
+		Name::CompilerPath,
+		Name::DefaultDownstreamCompiler,
+		Name::DumpRepro,
+		Name::DumpReproOnError,
+		Name::ExtractRepro,
+		Name::LoadRepro,
+		Name::LoadReproDirectory,
+		Name::ReproFallbackDirectory,
+		Name::ReproFileSystem,
+		Name::FileSystem,
+		Name::Heap,
+		Name::CompileCoreModule,
+		Name::Doc,
+		Name::IrCompression,
+		Name::LoadCoreModule,
+		Name::SaveCoreModule,
+		Name::SaveCoreModuleBinSource,
+		Name::SaveGLSLModuleBinSource,
+		Name::DumpModule,
+		Name::GetModuleInfo,
+		Name::GetSupportedModuleVersions,
+	];
+
+	let names = slang::CompilerOptions::NAMES
+		.iter()
+		.chain(&command_line_only)
+		.map(|&name| name as i32)
+		.collect::<Vec<_>>();
+	let unique = names
+		.iter()
+		.copied()
+		.collect::<std::collections::BTreeSet<_>>();
+
+	assert_eq!(names.len(), unique.len(), "an option is listed twice");
+
+	// Fails when the linked Slang version has an option without a builder method.
+	assert_eq!(
+		unique,
+		slang::sys::COMPILER_OPTION_NAMES
+			.iter()
+			.map(|&name| name as i32)
+			.collect(),
+		"every CompilerOptionName needs a CompilerOptions method"
+	);
+
+	let options = slang::CompilerOptions::default().vulkan_bind_shift(
+		slang::VulkanShiftKind::UnorderedAccess,
+		3,
+		16,
+	);
+
+	assert_eq!(options.options[0].value.intValue0, 2 << 24 | 3);
+	assert_eq!(options.options[0].value.intValue1, 16);
+}
+
+#[test]
+fn parse_command_line() {
+	let global_session = slang::GlobalSession::new().unwrap();
+
+	let desc = global_session
+		.parse_command_line(&[
+			"-O3",
+			"-matrix-layout-row-major",
+			"-target",
+			"spirv",
+			"-profile",
+			"glsl_450",
+			"-D",
+			"FOO=1",
+			"-I",
+			"shaders",
+		])
+		.unwrap();
+
+	assert_eq!(desc.desc().targetCount, 1);
+	let target = unsafe { &*desc.desc().targets };
+	assert_eq!(target.format, slang::CompileTarget::Spirv);
+
+	// Options may be stored on the session or on the target.
+	let entries = [
+		(
+			desc.desc().compilerOptionEntries,
+			desc.desc().compilerOptionEntryCount,
+		),
+		(
+			target.compilerOptionEntries,
+			target.compilerOptionEntryCount,
+		),
+	]
+	.into_iter()
+	.filter(|(_, count)| *count != 0)
+	.flat_map(|(entries, count)| unsafe { std::slice::from_raw_parts(entries, count as _) })
+	.collect::<Vec<_>>();
+
+	assert!(entries.iter().any(|entry| {
+		entry.name == slang::CompilerOptionName::Optimization
+			&& entry.value.intValue0 == slang::OptimizationLevel::Maximal as i32
+	}));
+
+	let session = global_session.create_session(&desc).unwrap();
+	let module = session.load_module("test.slang").unwrap();
+	let linked_program = link_main(&session, module);
+	assert_ne!(
+		linked_program
+			.entry_point_code(0, 0)
+			.unwrap()
+			.as_slice()
+			.len(),
+		0
+	);
+
+	// Only compiles if `-D FOO=1` was kept.
+	let module = session
+		.load_module_from_source_string(
+			"foo",
+			"foo.slang",
+			"#if FOO != 1\n#error FOO is not defined\n#endif\n\
+			 uniform float3x4 matrix;\n\
+			 [shader(\"compute\")] [numthreads(1, 1, 1)] void main() {}",
+		)
+		.unwrap();
+	let linked_program = link_main(&session, module);
+
+	let matrix = linked_program
+		.layout(0)
+		.unwrap()
+		.parameter_by_index(0)
+		.unwrap();
+	assert_eq!(
+		matrix.type_layout().unwrap().matrix_layout_mode(),
+		slang::MatrixLayoutMode::RowMajor
+	);
+
+	assert!(
+		global_session
+			.parse_command_line(&["-not-an-option"])
+			.is_err()
+	);
+}
+
+#[test]
+fn blob_to_hex() {
+	assert_eq!(
+		slang::Blob::from_bytes(&[0x00, 0x0f, 0xa5, 0xff]).to_hex(),
+		"000fa5ff"
+	);
+	assert_eq!(slang::Blob::from_bytes(&[]).to_hex(), "");
+}
+
+#[test]
+fn session_desc_digest() {
+	let global_session = slang::GlobalSession::new().unwrap();
+
+	let config = spirv_config(&global_session)
+		.search_path("shaders")
+		.unwrap();
+
+	let digest = global_session.session_desc_digest(&config).unwrap();
+	assert!(!digest.as_slice().is_empty());
+
+	let same = global_session.session_desc_digest(&config.clone()).unwrap();
+	assert_eq!(digest.to_hex(), same.to_hex());
+
+	let changed = config.preprocessor_macros(
+		slang::PreprocessorMacros::default()
+			.define("FOO", "1")
+			.unwrap(),
+	);
+	let changed = global_session.session_desc_digest(&changed).unwrap();
+	assert_ne!(digest.to_hex(), changed.to_hex());
+}